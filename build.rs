@@ -0,0 +1,53 @@
+//  Turns every `assets/<name>.obj` into a `models::<name>()` mesh.
+
+use std::{env, fs, path::Path, process};
+
+#[path = "src/obj.rs"]
+mod obj;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let assets_dir = Path::new(&manifest_dir).join("assets");
+    println!("cargo:rerun-if-changed=src/obj.rs");
+    println!("cargo:rerun-if-changed=src/palette.rs");
+    println!("cargo:rerun-if-changed={}", assets_dir.display());
+
+    let mut paths = fs::read_dir(&assets_dir)
+        .unwrap_or_else(|e| fail(&format!("{}: {}", assets_dir.display(), e)))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "obj"))
+        .collect::<Vec<_>>();
+    //  Keep the output stable no matter what order the file system lists things in.
+    paths.sort();
+
+    let mut out = String::new();
+    let mut max_vertices = 0;
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let name = path.file_stem().unwrap().to_string_lossy();
+        let model = obj::load(&path).unwrap_or_else(|e| fail(&e.to_string()));
+        let metadata = model.metadata();
+        for (line, warning) in &metadata.warnings {
+            println!("cargo:warning={}:{}: {}", path.display(), line, warning);
+        }
+        let source = model
+            .to_rust(&name, &metadata)
+            .unwrap_or_else(|e| fail(&format!("{}: {}", path.display(), e)));
+        out.push_str(&source);
+        max_vertices = max_vertices.max(model.vertices.len());
+    }
+    out.push_str("\n//  The most vertices in any one mesh, for sizing per vertex buffers.\n");
+    out.push_str(&format!(
+        "pub const MAX_VERTICES: usize = {};\n",
+        max_vertices
+    ));
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("models.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1);
+}
@@ -1,32 +1,46 @@
-//  Export as .obj with trianglulated mesh and everything else off.
+//  Prints the Rust source for a mesh, ready to paste or `include!`.
+//  Files dropped into `assets/` are picked up by `build.rs` instead, this is for a quick look.
 
-fn main() {
-    let arg = std::env::args().nth(1).unwrap();
-    let obj = std::fs::read_to_string(arg).unwrap();
-    let mut faces = vec![];
-    let mut vertices = vec![];
-    for line in obj.lines() {
-        let split = line.split(' ').collect::<Vec<_>>();
-        if split[0] == "v" {
-            vertices.push([
-                split[1].parse::<f32>().unwrap(),
-                split[2].parse::<f32>().unwrap(),
-                split[3].parse::<f32>().unwrap(),
-            ]);
-        } else if split[0] == "f" {
-            faces.push([
-                split[1].parse::<usize>().unwrap(),
-                split[2].parse::<usize>().unwrap(),
-                split[3].parse::<usize>().unwrap(),
-            ]);
-        }
-    }
+use std::path::Path;
 
-    let mut out = vec![];
+#[path = "../obj.rs"]
+mod obj;
 
-    for face in faces.into_iter().flatten() {
-        out.push(vertices[face - 1]);
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(arg) = args.next() else {
+        eprintln!("usage: load_obj <file.obj> [out.rs]");
+        std::process::exit(1);
+    };
+    let path = Path::new(&arg);
+    let obj = obj::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let metadata = obj.metadata();
+    for (line, warning) in &metadata.warnings {
+        eprintln!("warning: {}:{}: {}", arg, line, warning);
     }
+    eprintln!(
+        "{} triangles, bounds {:?} to {:?}, bounding sphere radius {}",
+        obj.faces.len(),
+        metadata.bounds_min,
+        metadata.bounds_max,
+        metadata.sphere_radius
+    );
 
-    eprintln!("{:?}", out.into_iter().flatten().collect::<Vec<_>>());
+    let name = path.file_stem().unwrap().to_string_lossy();
+    let source = obj.to_rust(&name, &metadata).unwrap_or_else(|e| {
+        eprintln!("{}: {}", arg, e);
+        std::process::exit(1);
+    });
+    if let Some(out) = args.next() {
+        std::fs::write(&out, source).unwrap_or_else(|e| {
+            eprintln!("{}: {}", out, e);
+            std::process::exit(1);
+        });
+    } else {
+        print!("{}", source);
+    }
 }
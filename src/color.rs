@@ -34,24 +34,37 @@ impl From<u8> for Color {
     }
 }
 
+pub const SHADE_LEVELS: usize = 4;
+
+//  Darkest to brightest, built only out of palette colors.
+//  The unlit color itself always sits at level 2.
+const SHADE_RAMPS: [[Color; SHADE_LEVELS]; Color::Orange9 as usize + 1] = [
+    [Color::Gray0, Color::Gray0, Color::Gray0, Color::GrayL],
+    [Color::Gray0, Color::Gray0, Color::GrayL, Color::Gray1],
+    [Color::Gray0, Color::GrayL, Color::Gray1, Color::Gray2],
+    [Color::GrayL, Color::Gray1, Color::Gray2, Color::Gray2],
+    [Color::Gray0, Color::RedC, Color::Red3, Color::Pink8],
+    [Color::Gray0, Color::Gray0, Color::RedC, Color::Red3],
+    [Color::Blue5, Color::Blue5, Color::Blue7, Color::Gray2],
+    [Color::Gray0, Color::Gray0, Color::Blue5, Color::Blue7],
+    [Color::RedC, Color::YellowF, Color::Yellow6, Color::Gray2],
+    [Color::Gray0, Color::GrayL, Color::YellowF, Color::Yellow6],
+    [Color::GreenD, Color::GreenD, Color::Green4, Color::Gray2],
+    [Color::Gray0, Color::Gray0, Color::GreenD, Color::Green4],
+    [Color::PurpleH, Color::PurpleH, Color::Pink8, Color::Gray2],
+    [Color::Gray0, Color::Blue5, Color::PurpleH, Color::Pink8],
+    [Color::RedC, Color::RedC, Color::Orange9, Color::Yellow6],
+];
+
+impl Color {
+    pub fn shade(self, level: usize) -> Color {
+        SHADE_RAMPS[self as usize][level.min(SHADE_LEVELS - 1)]
+    }
+}
+
 impl From<Color> for Rgb565 {
     fn from(value: Color) -> Self {
-        match value {
-            Color::Gray0 => Rgb565::from(Rgb888::new(0, 0, 0)),
-            Color::GrayL => Rgb565::from(Rgb888::new(73, 80, 87)),
-            Color::Gray1 => Rgb565::from(Rgb888::new(145, 151, 156)),
-            Color::Gray2 => Rgb565::from(Rgb888::new(248, 249, 250)),
-            Color::Red3 => Rgb565::from(Rgb888::new(235, 44, 71)),
-            Color::RedC => Rgb565::from(Rgb888::new(139, 65, 46)),
-            Color::Blue7 => Rgb565::from(Rgb888::new(25, 177, 248)),
-            Color::Blue5 => Rgb565::from(Rgb888::new(19, 21, 224)),
-            Color::Yellow6 => Rgb565::from(Rgb888::new(254, 230, 16)),
-            Color::YellowF => Rgb565::from(Rgb888::new(149, 140, 50)),
-            Color::Green4 => Rgb565::from(Rgb888::new(45, 225, 62)),
-            Color::GreenD => Rgb565::from(Rgb888::new(29, 148, 16)),
-            Color::Pink8 => Rgb565::from(Rgb888::new(245, 109, 187)),
-            Color::PurpleH => Rgb565::from(Rgb888::new(170, 58, 197)),
-            Color::Orange9 => Rgb565::from(Rgb888::new(245, 113, 23)),
-        }
+        let [r, g, b] = PALETTE[value as usize].1;
+        Rgb565::from(Rgb888::new(r, g, b))
     }
 }
@@ -1,12 +1,100 @@
 use super::*;
 
+impl Wall {
+    pub(super) fn aabb(&self) -> Aabb {
+        Aabb::from_center_extents(self.position, self.scale)
+    }
+}
+
+impl EnemyBuckets {
+    pub(super) fn new(positions: &[Vec3]) -> Self {
+        let mut buckets = EnemyBuckets {
+            first: [ENEMY_BUCKET_END; ENEMY_BUCKETS],
+            next: [ENEMY_BUCKET_END; ENEMY_BUCKET_CAPACITY],
+        };
+        for (i, &position) in positions.iter().enumerate().take(ENEMY_BUCKET_CAPACITY) {
+            let bucket = Self::bucket(Self::cell(position));
+            buckets.next[i] = buckets.first[bucket];
+            buckets.first[bucket] = i as u8;
+        }
+        buckets
+    }
+
+    //  How far to move to get clear of the enemies within `ENEMY_SEPARATION_RADIUS`, going half
+    //  the way since they move out of the way too, and no further than `ENEMY_SEPARATION_MAX_PUSH`.
+    //  Whatever part of `velocity` heads on into any of them is taken out, otherwise a crowd
+    //  walking in side by side squeezes together.
+    pub(super) fn separation(&self, positions: &[Vec3], enemy: usize, velocity: &mut Vec3) -> Vec3 {
+        let sphere = |i: usize| Sphere {
+            center: positions[i],
+            radius: ENEMY_SEPARATION_RADIUS * 0.5,
+        };
+        let mut push = Vec3::ZERO;
+        for other in self.near(positions[enemy]).filter(|&other| other != enemy) {
+            //  Right on top of each other the lower index goes off along +X, so ask from its side.
+            let contact = if enemy < other {
+                sphere(enemy).intersect_sphere(&sphere(other))
+            } else {
+                sphere(other)
+                    .intersect_sphere(&sphere(enemy))
+                    .map(|contact| Contact {
+                        normal: -contact.normal,
+                        ..contact
+                    })
+            };
+            if let Some(contact) = contact {
+                *velocity -= contact.normal * velocity.dot(contact.normal).min(0.0);
+                push += contact.normal * (contact.depth * 0.5);
+            }
+        }
+        let length = push.length();
+        if length > ENEMY_SEPARATION_MAX_PUSH {
+            push * (ENEMY_SEPARATION_MAX_PUSH / length)
+        } else {
+            push
+        }
+    }
+
+    //  Every enemy in the cells around `position`, plus any sharing their buckets.
+    fn near(&self, position: Vec3) -> impl Iterator<Item = usize> + '_ {
+        let (x, z) = Self::cell(position);
+        let mut buckets: SmallVec<[usize; 9]> = smallvec![];
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                let bucket = Self::bucket((x + dx, z + dz));
+                if !buckets.contains(&bucket) {
+                    buckets.push(bucket);
+                }
+            }
+        }
+        buckets.into_iter().flat_map(move |bucket| {
+            let first = Some(self.first[bucket]).filter(|&i| i != ENEMY_BUCKET_END);
+            core::iter::successors(first, |&i| {
+                Some(self.next[i as usize]).filter(|&i| i != ENEMY_BUCKET_END)
+            })
+            .map(usize::from)
+        })
+    }
+
+    fn cell(position: Vec3) -> (i32, i32) {
+        (
+            (position[0] / ENEMY_SEPARATION_RADIUS).floor() as i32,
+            (position[2] / ENEMY_SEPARATION_RADIUS).floor() as i32,
+        )
+    }
+
+    fn bucket((x, z): (i32, i32)) -> usize {
+        (x.wrapping_mul(73856093) ^ z.wrapping_mul(19349663)) as u32 as usize % ENEMY_BUCKETS
+    }
+}
+
 impl GamePlayState {
     //  Check if hit by bullet.
     //  Destroy the bullet if so.
     pub(super) fn hit_by_bullet(bullets: &mut SmallVec<[Bullet; 16]>, agent: Vec3) -> bool {
         let mut was_hit = false;
         bullets.retain(|bullet| {
-            if vec_distance(bullet.position, agent) < BULLET_HITBOX_RADIUS {
+            if bullet.position.distance(agent) < BULLET_HITBOX_RADIUS {
                 was_hit = true;
                 false
             } else {
@@ -16,24 +104,212 @@ impl GamePlayState {
         was_hit
     }
 
-    pub(super) fn get_collision_wall(walls: &SmallVec<[Wall; 64]>, position: Vec3) -> Option<Wall> {
+    //  The first wall crossed going from `start` to `end`.
+    pub(super) fn get_wall_hit(walls: &Walls, start: Vec3, end: Vec3) -> Option<(Wall, Hit)> {
         walls
-            .iter()
-            .find(|wall| {
-                let box_min_x =
-                    (wall.position[0] - wall.scale[0]).min(wall.position[0] + wall.scale[0]);
-                let box_max_x =
-                    (wall.position[0] - wall.scale[0]).max(wall.position[0] + wall.scale[0]);
-                let box_min_z =
-                    (wall.position[2] - wall.scale[2]).min(wall.position[2] + wall.scale[2]);
-                let box_max_z =
-                    (wall.position[2] - wall.scale[2]).max(wall.position[2] + wall.scale[2]);
-
-                position[0] >= box_min_x
-                    && position[0] <= box_max_x
-                    && position[2] >= box_min_z
-                    && position[2] <= box_max_z
+            .along_segment(start, end)
+            .filter_map(|wall| Some((*wall, wall.aabb().intersect_segment(start, end)?)))
+            .min_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance))
+    }
+
+    //  The deepest wall a circle of `radius` around `position` sinks into.
+    //  Walls count as infinitely tall, so the normal always lies on the floor.
+    pub(super) fn get_wall_contact(walls: &Walls, position: Vec3, radius: f32) -> Option<Contact> {
+        let circle = Sphere {
+            center: position,
+            radius,
+        };
+        let reach = Vec3::new(radius, 0.0, radius);
+        let near = Aabb {
+            min: position - reach,
+            max: position + reach,
+        };
+        walls
+            .in_aabb(&near)
+            .filter_map(|wall| {
+                let mut aabb = wall.aabb();
+                aabb.min[1] = f32::MIN;
+                aabb.max[1] = f32::MAX;
+                circle.intersect_aabb(&aabb)
             })
-            .copied()
+            .max_by(|a, b| a.depth.total_cmp(&b.depth))
+    }
+
+    //  Push a circle moving from `from` to `position` out of every wall it sinks into and drop
+    //  the part of `velocity` going into them, returning where it ends up.
+    //  Squeezed into a gap too narrow for it, the passes push it from one side to the other, so
+    //  it stays at `from` instead.
+    pub(super) fn slide_against_walls(
+        walls: &Walls,
+        from: Vec3,
+        mut position: Vec3,
+        velocity: &mut Vec3,
+        radius: f32,
+    ) -> Vec3 {
+        for _ in 0..MAP_WALL_ITERATIONS {
+            let Some(contact) = Self::get_wall_contact(walls, position, radius) else {
+                break;
+            };
+            position += contact.normal * contact.depth;
+            let into_wall = velocity.dot(contact.normal);
+            if into_wall < 0.0 {
+                *velocity -= contact.normal * into_wall;
+            }
+        }
+        if Self::get_wall_contact(walls, position, radius)
+            .is_some_and(|contact| contact.depth > MAP_WALL_TOLERANCE)
+        {
+            return from;
+        }
+        position
+    }
+
+    //  Which way to walk to get to `target`, going around the first wall ahead rather than into it.
+    pub(super) fn steer_around_walls(walls: &Walls, position: Vec3, target: Vec3) -> Vec3 {
+        let direction = (target - position).normalize_or_zero();
+        let look_ahead = position + direction * ENEMY_AVOID_DISTANCE;
+        let Some((wall, _)) = Self::get_wall_hit(walls, position, look_ahead) else {
+            return direction;
+        };
+
+        //  Head for the corner of the wall that makes for the shortest trip around it, skipping
+        //  the ones already reached or hidden behind the wall. They sit two radii out so that
+        //  anywhere near one has a clear line to the next.
+        let aabb = wall.aabb();
+        let half_x = wall.scale[0].abs() + ENEMY_RADIUS * 2.0;
+        let half_z = wall.scale[2].abs() + ENEMY_RADIUS * 2.0;
+        //  In order going around the wall.
+        let corners = [(-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (1.0, -1.0)]
+            .map(|(x, z)| wall.position + Vec3::new(half_x * x, 0.0, half_z * z));
+        let clear = |from: Vec3, to: Vec3| aabb.intersect_segment(from, to).is_none();
+        //  From a corner, either straight to the target or by way of a neighbouring corner.
+        let rest_of_trip = |i: usize| {
+            let corner = corners[i];
+            if clear(corner, target) {
+                return corner.distance(target);
+            }
+            [corners[(i + 1) % 4], corners[(i + 3) % 4]]
+                .map(|next| corner.distance(next) + next.distance(target))
+                .into_iter()
+                .fold(f32::MAX, f32::min)
+        };
+        (0..4)
+            .filter(|&i| {
+                position.distance(corners[i]) >= ENEMY_RADIUS && clear(position, corners[i])
+            })
+            .map(|i| (corners[i], position.distance(corners[i]) + rest_of_trip(i)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(direction, |(corner, _)| {
+                (corner - position).normalize_or_zero()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(position: (f32, f32), scale: (f32, f32)) -> Wall {
+        Wall {
+            scale: Vec3::new(scale.0, MAP_WALL_Y, scale.1),
+            position: Vec3::new(position.0, 0.0, position.1),
+        }
+    }
+
+    //  The border, a few blocks, and a wall across z = 10 with a gap in front of the player
+    //  narrower than an enemy.
+    fn layout() -> [Wall; 9] {
+        let bound = MAP_SETTINGS[0].bound;
+        [
+            wall((bound, 0.0), (2.0, bound)),
+            wall((-bound, 0.0), (2.0, bound)),
+            wall((0.0, -bound), (bound, 2.0)),
+            wall((0.0, bound), (bound, 2.0)),
+            wall((-15.3, 10.0), (14.7, 1.0)),
+            wall((15.3, 10.0), (14.7, 1.0)),
+            wall((20.0, 30.0), (4.0, 4.0)),
+            wall((-25.0, -20.0), (3.0, 6.0)),
+            wall((0.0, -30.0), (6.0, 2.0)),
+        ]
+    }
+
+    fn depth_into_walls(walls: &Walls, position: Vec3, radius: f32) -> f32 {
+        GamePlayState::get_wall_contact(walls, position, radius)
+            .map_or(0.0, |contact| contact.depth)
+    }
+
+    #[test]
+    fn squeezing_into_a_gap_stays_put() {
+        let state = GamePlayState::with_walls(&layout());
+        let from = Vec3::new(0.0, 0.0, 14.0);
+        let mut velocity = Vec3::new(0.0, 0.0, -4.0);
+        let position = GamePlayState::slide_against_walls(
+            &state.walls,
+            from,
+            Vec3::new(0.0, 0.0, 10.0),
+            &mut velocity,
+            ENEMY_RADIUS,
+        );
+        assert_eq!(position, from);
+    }
+
+    //  Enemies walking at the player from all around, several straight at the gap, never end
+    //  a tick inside a wall.
+    #[test]
+    fn enemies_never_end_a_tick_in_a_wall() {
+        let mut state = GamePlayState::with_walls(&layout());
+        let starts = [
+            (-4.0, 20.0),
+            (-2.0, 20.0),
+            (0.0, 20.0),
+            (2.0, 20.0),
+            (4.0, 20.0),
+            (0.0, 14.0),
+            (20.0, 40.0),
+            (-25.0, -30.0),
+            (0.0, -40.0),
+            (40.0, 0.0),
+        ];
+        for (x, z) in starts {
+            state.enemies.push(Enemy {
+                speed: 0.5,
+                health: 100.0,
+                color: Color::Red3,
+                position: Vec3::new(x, 0.0, z),
+                dodge_entropy: 0.0,
+            });
+        }
+
+        for tick in 0..300 {
+            state.update(Buttons::empty());
+            for enemy in &state.enemies {
+                let depth = depth_into_walls(&state.walls, enemy.position, ENEMY_RADIUS);
+                assert!(
+                    depth <= MAP_WALL_TOLERANCE,
+                    "tick {}: {:?} is {} into a wall",
+                    tick,
+                    enemy.position,
+                    depth
+                );
+            }
+            assert!(depth_into_walls(&state.walls, state.player.position, PLAYER_RADIUS) <= 0.0);
+        }
+    }
+
+    //  Right on top of each other, the lower index goes off along +X and the other the opposite
+    //  way, without either walking on into the other.
+    #[test]
+    fn enemies_on_top_of_each_other_split_by_index() {
+        let positions = [Vec3::new(3.0, 0.0, -2.0); 2];
+        let buckets = EnemyBuckets::new(&positions);
+        let mut velocity = Vec3::new(-0.3, 0.0, 0.0);
+        let first = buckets.separation(&positions, 0, &mut velocity);
+        assert_eq!(first, Vec3::new(ENEMY_SEPARATION_MAX_PUSH, 0.0, 0.0));
+        assert_eq!(velocity, Vec3::ZERO);
+
+        let mut velocity = Vec3::new(0.0, 0.0, 0.3);
+        let second = buckets.separation(&positions, 1, &mut velocity);
+        assert_eq!(second, -first);
+        assert_eq!(velocity, Vec3::new(0.0, 0.0, 0.3));
     }
 }
@@ -0,0 +1,248 @@
+use super::*;
+
+impl Walls {
+    pub(super) fn new() -> Self {
+        Walls {
+            list: smallvec![],
+            origin: Vec3::ZERO,
+            cell_size: 1.0,
+            cells: [0; WALL_GRID_CELLS],
+            #[cfg(test)]
+            checks: core::cell::Cell::new((0, 0)),
+        }
+    }
+
+    //  Lay the grid over the map out past the border walls and mark the cells each wall touches.
+    //  Has to run again whenever `list` changes.
+    pub(super) fn build(&mut self, bound: f32) {
+        assert!(
+            self.list.len() <= u64::BITS as usize,
+            "{} walls, but a grid cell only has a bit for each of 64",
+            self.list.len()
+        );
+        let extent = bound + 4.0;
+        self.origin = Vec3::new(-extent, 0.0, -extent);
+        self.cell_size = extent * 2.0 / WALL_GRID_SIZE as f32;
+        self.cells = [0; WALL_GRID_CELLS];
+        for (i, wall) in self.list.iter().enumerate() {
+            let aabb = wall.aabb();
+            let (min_x, min_z) = self.cell_of(aabb.min);
+            let (max_x, max_z) = self.cell_of(aabb.max);
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    self.cells[z * WALL_GRID_SIZE + x] |= 1 << i;
+                }
+            }
+        }
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Wall> {
+        self.list.iter()
+    }
+
+    pub(super) fn in_aabb(&self, aabb: &Aabb) -> impl Iterator<Item = &Wall> {
+        self.masked(self.aabb_mask(aabb))
+    }
+
+    pub(super) fn along_segment(&self, start: Vec3, end: Vec3) -> impl Iterator<Item = &Wall> {
+        self.masked(self.segment_mask(start, end))
+    }
+
+    //  Walls in cells that a level camera might see, out to `far`. Anything else, like the
+    //  death animation looking down, gets every wall.
+    pub(super) fn in_view(
+        &self,
+        position: Vec3,
+        front: Vec3,
+        tan_half_fov: f32,
+        far: f32,
+    ) -> impl Iterator<Item = &Wall> {
+        let forward = Vec3::new(front[0], 0.0, front[2]);
+        let Some(forward) = forward.try_normalize().filter(|_| front[1].abs() < 0.01) else {
+            return self.masked(u64::MAX);
+        };
+
+        //  Outward normals of the side planes, which go through the camera.
+        let right = forward.cross(Vec3::new(0.0, 1.0, 0.0));
+        let right_side = right - forward * tan_half_fov;
+        let left_side = -right - forward * tan_half_fov;
+
+        //  The far plane is `far` ahead, its corners are further out to the sides.
+        let reach = far * (1.0 + tan_half_fov * tan_half_fov).sqrt();
+        let reach = Vec3::new(reach, 0.0, reach);
+        let (min_x, min_z) = self.cell_of(position - reach);
+        let (max_x, max_z) = self.cell_of(position + reach);
+        let mut mask = 0;
+        for z in min_z..=max_z {
+            for x in min_x..=max_x {
+                let cell_min = self.origin + Vec3::new(x as f32, 0.0, z as f32) * self.cell_size;
+                let corners = [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)]
+                    .map(|(x, z)| cell_min + Vec3::new(x, 0.0, z) * self.cell_size - position);
+                //  Out of view when every corner is past the same plane.
+                let outside = |normal: Vec3, distance: f32| {
+                    corners.iter().all(|&corner| corner.dot(normal) > distance)
+                };
+                if !(outside(right_side, 0.0) || outside(left_side, 0.0) || outside(forward, far)) {
+                    mask |= self.cells[z * WALL_GRID_SIZE + x];
+                }
+            }
+        }
+        self.masked(mask)
+    }
+
+    fn aabb_mask(&self, aabb: &Aabb) -> u64 {
+        let (min_x, min_z) = self.cell_of(aabb.min);
+        let (max_x, max_z) = self.cell_of(aabb.max);
+        let mut mask = 0;
+        for z in min_z..=max_z {
+            for x in min_x..=max_x {
+                mask |= self.cells[z * WALL_GRID_SIZE + x];
+            }
+        }
+        mask
+    }
+
+    //  Walks the cells the segment passes over, one boundary at a time.
+    fn segment_mask(&self, start: Vec3, end: Vec3) -> u64 {
+        let extent = self.cell_size * WALL_GRID_SIZE as f32;
+        let inside = |point: Vec3| {
+            [0, 2].into_iter().all(|axis| {
+                let offset = point[axis] - self.origin[axis];
+                (0.0..extent).contains(&offset)
+            })
+        };
+        //  Off the grid the boundaries don't line up with the cells any more.
+        if !inside(start) || !inside(end) {
+            let mut aabb = Aabb {
+                min: start,
+                max: end,
+            };
+            for axis in 0..3 {
+                if end[axis] < start[axis] {
+                    aabb.min[axis] = end[axis];
+                    aabb.max[axis] = start[axis];
+                }
+            }
+            return self.aabb_mask(&aabb);
+        }
+
+        let (mut x, mut z) = self.cell_of(start);
+        let end_cell = self.cell_of(end);
+        let delta = end - start;
+        //  How far along the segment, from 0.0 to 1.0, the next boundary on an axis is crossed,
+        //  and how much further each one after that.
+        let crossing = |cell: usize, axis: usize| {
+            if delta[axis] == 0.0 {
+                return (f32::MAX, f32::MAX);
+            }
+            let boundary = cell as f32 + if delta[axis] > 0.0 { 1.0 } else { 0.0 };
+            let boundary = self.origin[axis] + boundary * self.cell_size;
+            (
+                (boundary - start[axis]) / delta[axis],
+                self.cell_size / delta[axis].abs(),
+            )
+        };
+        let (mut next_x, step_x) = crossing(x, 0);
+        let (mut next_z, step_z) = crossing(z, 2);
+
+        let mut mask = self.cells[z * WALL_GRID_SIZE + x];
+        //  Every step moves one cell closer to the end, this only bounds float error.
+        for _ in 0..WALL_GRID_SIZE * 2 {
+            if (x, z) == end_cell {
+                break;
+            }
+            if next_x < next_z {
+                x = if delta[0] > 0.0 {
+                    x + 1
+                } else {
+                    x.wrapping_sub(1)
+                };
+                next_x += step_x;
+            } else {
+                z = if delta[2] > 0.0 {
+                    z + 1
+                } else {
+                    z.wrapping_sub(1)
+                };
+                next_z += step_z;
+            }
+            if x >= WALL_GRID_SIZE || z >= WALL_GRID_SIZE {
+                break;
+            }
+            mask |= self.cells[z * WALL_GRID_SIZE + x];
+        }
+        mask
+    }
+
+    //  Clamped to the grid, so anything past the edge lands in the edge cells.
+    fn cell_of(&self, point: Vec3) -> (usize, usize) {
+        let cell = |axis: usize| {
+            (((point[axis] - self.origin[axis]) / self.cell_size) as usize).min(WALL_GRID_SIZE - 1)
+        };
+        (cell(0), cell(2))
+    }
+
+    fn masked(&self, mask: u64) -> impl Iterator<Item = &Wall> {
+        #[cfg(test)]
+        {
+            let (lookups, walls) = self.checks.get();
+            self.checks
+                .set((lookups + 1, walls + mask.count_ones() as usize));
+        }
+        let first = Some(mask).filter(|&mask| mask != 0);
+        core::iter::successors(first, |&mask| {
+            Some(mask & (mask - 1)).filter(|&mask| mask != 0)
+        })
+        .filter_map(|mask| self.list.get(mask.trailing_zeros() as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //  Without the grid, every lookup would go through every wall. Over a seeded game it should
+    //  turn up a small part of that, every tick.
+    #[test]
+    fn grid_checks_fewer_walls_than_brute_force() {
+        let mut state = GamePlayState::with_seed(24);
+        state.init();
+        let walls = state.walls.list.len();
+
+        let (mut lookups, mut checked) = (0, 0);
+        for tick in 0..600 {
+            state.walls.checks.set((0, 0));
+            state.update(Buttons::empty());
+            let (tick_lookups, tick_checked) = state.walls.checks.get();
+            assert!(
+                tick_checked < tick_lookups * walls,
+                "tick {}: {} walls for {} lookups",
+                tick,
+                tick_checked,
+                tick_lookups
+            );
+            lookups += tick_lookups;
+            checked += tick_checked;
+        }
+        assert!(
+            checked * 10 < lookups * walls,
+            "{} of {}",
+            checked,
+            lookups * walls
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "65 walls, but a grid cell only has a bit for each of 64")]
+    fn more_walls_than_mask_bits_are_refused() {
+        let wall = Wall {
+            scale: Vec3::new(1.0, 1.0, 1.0),
+            position: Vec3::ZERO,
+        };
+        let mut walls = Walls::new();
+        walls.list.extend(core::iter::repeat_n(wall, 64));
+        walls.build(MAP_SETTINGS[0].bound);
+        walls.list.push(wall);
+        walls.build(MAP_SETTINGS[0].bound);
+    }
+}
@@ -4,37 +4,21 @@ impl GamePlayState {
     pub fn tick_buttons(&mut self, buttons: Buttons) {
         if buttons.contains(Buttons::W) {
             self.player_bob();
-            self.player.velocity = vec_add_vec(
-                self.player.velocity,
-                vec_mul_scalar(self.player.direction, PLAYER_ACCELERATION),
-            )
+            self.player.velocity += self.player.direction * PLAYER_ACCELERATION;
         }
         if buttons.contains(Buttons::S) {
             self.player_bob();
-            self.player.velocity = vec_add_vec(
-                self.player.velocity,
-                vec_mul_scalar(self.player.direction, -PLAYER_ACCELERATION),
-            )
+            self.player.velocity += self.player.direction * -PLAYER_ACCELERATION;
         }
         if buttons.contains(Buttons::A) {
             self.player_bob();
-            self.player.velocity = vec_add_vec(
-                self.player.velocity,
-                vec_mul_scalar(
-                    vec_normalize(vec3_cross_product(self.player.direction, [0.0, 1.0, 0.0])),
-                    PLAYER_ACCELERATION,
-                ),
-            );
+            let right = self.player.direction.cross(Vec3::new(0.0, 1.0, 0.0));
+            self.player.velocity += right.normalize_or_zero() * PLAYER_ACCELERATION;
         }
         if buttons.contains(Buttons::D) {
             self.player_bob();
-            self.player.velocity = vec_add_vec(
-                self.player.velocity,
-                vec_mul_scalar(
-                    vec_normalize(vec3_cross_product(self.player.direction, [0.0, 1.0, 0.0])),
-                    -PLAYER_ACCELERATION,
-                ),
-            );
+            let right = self.player.direction.cross(Vec3::new(0.0, 1.0, 0.0));
+            self.player.velocity += right.normalize_or_zero() * -PLAYER_ACCELERATION;
         }
         if buttons.contains(Buttons::I)
             && self.ticks - self.player.last_bullet_time >= BULLET_COOLDOWN_THRESHOLD
@@ -53,6 +37,49 @@ impl GamePlayState {
         }
     }
 
+    //  Up and down change the selected digit of the seed, left and right select another, and
+    //  shooting starts the game.
+    pub(super) fn tick_title(&mut self, buttons: Buttons) {
+        let Some(mut title) = self.title else {
+            return;
+        };
+        title.ticks += 1;
+        let held = title.held;
+        let pressed = |button: Buttons| buttons.contains(button) && !held.contains(button);
+
+        //  Nobody presses buttons on the same tick twice, which makes for a different seed on
+        //  every run unless one is picked.
+        if buttons != held {
+            title.entropy = WyRand::new_seed(title.entropy ^ title.ticks as u64).generate::<u64>();
+        }
+
+        let step = if pressed(Buttons::W) {
+            1
+        } else if pressed(Buttons::S) {
+            9
+        } else {
+            0
+        };
+        if step != 0 {
+            let seed = self.seed.get_or_insert(Self::seed_from(title.entropy));
+            let place = 10u32.pow((SEED_DIGITS - 1 - title.cursor) as u32);
+            let digit = *seed / place % 10;
+            *seed = *seed - digit * place + (digit + step) % 10 * place;
+        }
+        if pressed(Buttons::A) {
+            title.cursor = title.cursor.saturating_sub(1);
+        }
+        if pressed(Buttons::D) {
+            title.cursor = (title.cursor + 1).min(SEED_DIGITS - 1);
+        }
+
+        title.held = buttons;
+        self.title = Some(title);
+        if pressed(Buttons::I) {
+            self.init();
+        }
+    }
+
     fn player_bob(&mut self) {
         self.player.bob_tick += 1;
         self.player.position[1] = (self.player.bob_tick as f32 * 0.8).sin() * 0.2 + 0.1
@@ -1,7 +1,10 @@
 use super::*;
+use nanorand::{Rng, WyRand};
 
 mod collision;
+mod grid;
 mod input;
+mod navigation;
 mod render;
 mod spawn;
 mod text;
@@ -19,11 +22,14 @@ const BULLET_HITBOX_RADIUS: f32 = 2.5;
 const BULLET_COOLDOWN_THRESHOLD: usize = 5;
 const BULLET_MAX_DISTANCE: f32 = 40.0;
 const BULLET_SPEED: f32 = 1.0;
+//  Size of the puff left where a bullet hits a wall.
+const BULLET_IMPACT_SIZE: f32 = 0.8;
 
 #[derive(Clone, Copy)]
 struct Explosion {
     position: Vec3,
     size_scalar: f32,
+    max_size: f32,
 }
 
 const EXPLOSION_MAX_SIZE: f32 = 2.0;
@@ -39,6 +45,7 @@ struct Player {
     bob_tick: usize,
     yaw: f32,
     last_bullet_time: usize,
+    wall_response: WallResponse,
 }
 
 const PLAYER_MAX_HEALTH: f32 = 50.0;
@@ -46,8 +53,23 @@ const PLAYER_POISON_TICK: f32 = 0.01;
 const PLAYER_MAX_VELOCITY: f32 = 0.6;
 const PLAYER_FRICTION_SCALAR: f32 = 0.01;
 const PLAYER_WALL_BOUNCE_SCALAR: f32 = 0.8;
+//  Pick per build, `ROOM_WALL_RESPONSE=bounce` to bounce off walls rather than slide along them.
+const PLAYER_WALL_RESPONSE: WallResponse = match option_env!("ROOM_WALL_RESPONSE") {
+    Some(value) if matches!(value.as_bytes(), b"bounce") => WallResponse::Bounce,
+    _ => WallResponse::Slide,
+};
+const PLAYER_RADIUS: f32 = 1.0;
 const PLAYER_ACCELERATION: f32 = 0.1;
 
+//  What the player does when running into a wall.
+#[derive(Clone, Copy, PartialEq)]
+enum WallResponse {
+    //  Keep the speed along the wall and lose only the part going into it.
+    Slide,
+    //  Reflect off the wall, losing some speed.
+    Bounce,
+}
+
 #[derive(Clone, Copy)]
 struct Medkit {
     position: Vec3,
@@ -78,10 +100,31 @@ struct Enemy {
 const ENEMY_DAMAGE: f32 = 0.2;
 const ENEMY_MAX_DODGE: f32 = 0.3;
 const ENEMY_REACH: f32 = 3.0;
+const ENEMY_RADIUS: f32 = 1.0;
+//  How far ahead an enemy looks for walls to steer around.
+const ENEMY_AVOID_DISTANCE: f32 = 6.0;
+//  Where enemies gather around the player, just inside `ENEMY_REACH` so they can attack.
+const ENEMY_RING_RADIUS: f32 = ENEMY_REACH - 0.5;
+//  Enemies closer than this push each other apart.
+const ENEMY_SEPARATION_RADIUS: f32 = 2.5;
+//  The most an enemy is pushed in one tick, so a crush of them can't throw one past another.
+const ENEMY_SEPARATION_MAX_PUSH: f32 = 0.5;
 const ENEMY_SPEED_INCREMENT_SCALAR: f32 = 0.0006;
 const ENEMY_PLAYER_SPAWN_MIN_RADIUS: f32 = 60.0;
 const ENEMY_PLAYER_SPAWN_MAX_RADIUS: f32 = 350.0;
 
+//  Enemies hashed by position into cells `ENEMY_SEPARATION_RADIUS` wide, so finding the ones
+//  nearby doesn't mean looking at every one of them.
+struct EnemyBuckets {
+    first: [u8; ENEMY_BUCKETS],
+    next: [u8; ENEMY_BUCKET_CAPACITY],
+}
+
+const ENEMY_BUCKETS: usize = 64;
+//  As many as `GamePlayState::enemies` holds inline, the rest don't get pushed around.
+const ENEMY_BUCKET_CAPACITY: usize = 64;
+const ENEMY_BUCKET_END: u8 = u8::MAX;
+
 #[derive(Clone, Copy)]
 struct MapSetting {
     bound: f32,
@@ -91,6 +134,10 @@ struct MapSetting {
 
 const MAP_WALL_Y: f32 = 6.0;
 const MAP_WALL_TO_WALL_MIN_DISTANCE: f32 = 20.0;
+//  Enough passes to get out of a corner between two walls.
+const MAP_WALL_ITERATIONS: usize = 3;
+//  Pushed out to just touching, rounding can leave a circle this far into a wall.
+const MAP_WALL_TOLERANCE: f32 = 0.001;
 const MAP_SETTINGS: &[MapSetting] = &[MapSetting {
     bound: 100.0,
     wall_count: 40,
@@ -103,6 +150,57 @@ pub struct Wall {
     position: Vec3,
 }
 
+//  Grid laid over the whole map that enemies find their way around walls with.
+//  Sized for 50 enemies at `KILL_SCREEN_STAGE` sharing one flow field.
+struct FlowField {
+    origin: Vec3,
+    cell_size: f32,
+    blocked: [bool; NAV_CELLS],
+    //  Steps to the player's cell, `NAV_UNREACHABLE` where there's no way through.
+    distance: [u16; NAV_CELLS],
+    queue: [u16; NAV_CELLS],
+}
+
+const NAV_GRID_SIZE: usize = 52;
+const NAV_CELLS: usize = NAV_GRID_SIZE * NAV_GRID_SIZE;
+const NAV_UNREACHABLE: u16 = u16::MAX;
+//  Ticks between refreshes of the flow field towards the player.
+const NAV_FLOW_FIELD_INTERVAL: usize = 8;
+
+//  Every wall, with a uniform grid over them so collision and rendering only look at the ones
+//  nearby. Each cell is a mask of the walls touching it, one bit per wall.
+struct Walls {
+    list: SmallVec<[Wall; 64]>,
+    origin: Vec3,
+    cell_size: f32,
+    cells: [u64; WALL_GRID_CELLS],
+    //  Grid lookups so far and how many walls they turned up, to hold against checking every
+    //  wall every time.
+    #[cfg(test)]
+    checks: core::cell::Cell<(usize, usize)>,
+}
+
+const WALL_GRID_SIZE: usize = 16;
+const WALL_GRID_CELLS: usize = WALL_GRID_SIZE * WALL_GRID_SIZE;
+
+//  Picking the seed on the title screen, before the game starts.
+#[derive(Clone, Copy)]
+struct Title {
+    //  Buttons held last tick, so holding one down only counts once.
+    held: Buttons,
+    //  Which digit of the seed is being changed, counting from the left.
+    cursor: usize,
+    ticks: usize,
+    //  Mixed from when buttons get pressed, for a different game every time the seed is left
+    //  alone.
+    entropy: u64,
+}
+
+//  Seeds are kept to this many decimal digits so they can be read off and typed in on the
+//  title screen.
+const SEED_DIGITS: usize = 6;
+pub const SEED_LIMIT: u32 = 10u32.pow(SEED_DIGITS as u32);
+
 const ENEMY_CAP_STAGES: &[usize] = &[3, 5, 8, 10, 15, 18, 50];
 const ENEMY_SPAWN_FREQUENCY_PARAM: usize = 3;
 const KILL_SCREEN_STAGE: usize = ENEMY_CAP_STAGES.len() - 1;
@@ -125,50 +223,166 @@ pub struct GamePlayState {
     medkit: Option<Medkit>,
     enemies: SmallVec<[Enemy; 64]>,
     selected_map: usize,
-    walls: SmallVec<[Wall; 64]>,
+    walls: Walls,
     bullets: SmallVec<[Bullet; 16]>,
     explosions: SmallVec<[Explosion; 8]>,
+    flow_field: FlowField,
+    //  `None` until picked on the title screen or the game starts.
+    seed: Option<u32>,
+    rng: WyRand,
+    //  `None` once the game has started.
+    title: Option<Title>,
 }
 
 impl GamePlayState {
+    //  Takes the seed from button timing on the title screen.
     pub fn new() -> Self {
+        Self::with_optional_seed(None)
+    }
+
+    //  Same seed, same game. Seeds wrap around at `SEED_LIMIT`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self::with_optional_seed(Some(seed % SEED_LIMIT))
+    }
+
+    fn with_optional_seed(seed: Option<u32>) -> Self {
         GamePlayState {
             ticks: 0,
             player: Player {
                 dead: false,
-                position: [0.0, 0.0, 0.0],
-                velocity: [0.0, 0.0, 0.0],
-                direction: [1.0, 0.0, 0.0],
+                position: Vec3::ZERO,
+                velocity: Vec3::ZERO,
+                direction: Vec3::new(1.0, 0.0, 0.0),
                 health: PLAYER_MAX_HEALTH,
                 bob_tick: 0,
                 yaw: 0.0,
                 last_bullet_time: 0,
+                wall_response: PLAYER_WALL_RESPONSE,
             },
             medkit: None,
             enemies: smallvec![],
             selected_map: 0,
-            walls: smallvec![],
+            walls: Walls::new(),
             bullets: smallvec![],
             explosions: smallvec![],
+            flow_field: FlowField::new(),
+            seed,
+            rng: WyRand::new_seed(0),
+            title: Some(Title {
+                held: Buttons::empty(),
+                cursor: 0,
+                ticks: 0,
+                entropy: 0,
+            }),
         }
     }
 
+    //  Leave the title screen and start the game.
     pub fn init(&mut self) {
+        let entropy = self.title.map_or(0, |title| title.entropy);
+        let seed = *self.seed.get_or_insert(Self::seed_from(entropy));
+        self.rng = WyRand::new_seed(seed as u64);
+        self.title = None;
+
         self.spawn_walls();
-        self.spawn_medkit(Some([6.0, 0.0, 0.0]));
+        self.spawn_medkit(Some(Vec3::new(6.0, 0.0, 0.0)));
+    }
+
+    pub fn on_title(&self) -> bool {
+        self.title.is_some()
+    }
+
+    fn seed_from(entropy: u64) -> u32 {
+        (entropy % SEED_LIMIT as u64) as u32
+    }
+
+    fn rand_f32(&mut self) -> f32 {
+        self.rng.generate::<f32>()
     }
 }
 
-use core::cell::UnsafeCell;
-use nanorand::{Rng, WyRand};
+#[cfg(test)]
+impl GamePlayState {
+    //  Past the title screen with only `walls` on the map, and the player alone at the origin.
+    fn with_walls(walls: &[Wall]) -> Self {
+        let mut state = Self::with_seed(0);
+        state.title = None;
+        state.walls.list = walls.iter().copied().collect();
+        state.walls.build(MAP_SETTINGS[0].bound);
+        state.flow_field.build(&state.walls, MAP_SETTINGS[0].bound);
+        state.flow_field.flow_to(state.player.position);
+        state
+    }
+}
 
-static mut RAND: UnsafeCell<Option<WyRand>> = UnsafeCell::new(None);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn rand_f32() -> f32 {
-    let rand = unsafe { &mut *RAND.get() };
-    if rand.is_none() {
-        *rand = Some(WyRand::new_seed(9999999999));
+    const SEEDS: u32 = 8;
+    const TICKS: usize = 800;
+
+    //  Plays a game from `seed` with the same made up input every time, and sums up how it went.
+    fn play(seed: u32) -> (usize, Vec3, f32, usize, Vec3) {
+        let mut state = GamePlayState::with_seed(seed);
+        state.init();
+        let inputs = [
+            Buttons::W,
+            Buttons::I,
+            Buttons::J,
+            Buttons::W,
+            Buttons::I,
+            Buttons::D,
+            Buttons::L,
+            Buttons::S,
+            Buttons::K,
+            Buttons::A,
+            Buttons::empty(),
+        ];
+        for tick in 0..TICKS {
+            state.update(inputs[tick / 15 % inputs.len()]);
+        }
+        let enemies = state
+            .enemies
+            .iter()
+            .fold(Vec3::ZERO, |sum, enemy| sum + enemy.position);
+        (
+            state.ticks,
+            state.player.position,
+            state.player.health,
+            state.enemies.len(),
+            enemies,
+        )
+    }
+
+    //  Each game owns its RNG, so games running side by side can't change each other, and the
+    //  same seed always plays out the same.
+    #[test]
+    fn seeded_games_play_out_the_same_in_parallel() {
+        let run = || {
+            std::thread::scope(|scope| {
+                let games = (0..SEEDS)
+                    .map(|seed| {
+                        std::thread::Builder::new()
+                            .stack_size(8 * 1024 * 1024)
+                            .spawn_scoped(scope, move || play(seed))
+                            .unwrap()
+                    })
+                    .collect::<std::vec::Vec<_>>();
+                games
+                    .into_iter()
+                    .map(|game| game.join().unwrap())
+                    .collect::<std::vec::Vec<_>>()
+            })
+        };
+
+        let first = run();
+        let second = run();
+        assert_eq!(first, second);
+        assert_eq!(first[3], play(3));
+        //  Different seeds should make for different games.
+        for (seed, game) in first.iter().enumerate().skip(1) {
+            assert_ne!(*game, first[0], "seed {} played like seed 0", seed);
+        }
     }
-    let rand = rand.as_mut().unwrap();
-    rand.generate::<f32>()
 }
@@ -0,0 +1,108 @@
+use super::*;
+
+//  Orthogonal steps first, so ties go to the straighter path.
+const NAV_NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+impl FlowField {
+    pub(super) fn new() -> Self {
+        FlowField {
+            origin: Vec3::ZERO,
+            cell_size: 1.0,
+            blocked: [false; NAV_CELLS],
+            distance: [NAV_UNREACHABLE; NAV_CELLS],
+            queue: [0; NAV_CELLS],
+        }
+    }
+
+    //  Cover the map out past the border walls and block every cell an enemy can't stand in
+    //  the middle of.
+    pub(super) fn build(&mut self, walls: &Walls, bound: f32) {
+        let extent = bound + 4.0;
+        self.origin = Vec3::new(-extent, 0.0, -extent);
+        self.cell_size = extent * 2.0 / NAV_GRID_SIZE as f32;
+        for cell in 0..NAV_CELLS {
+            let center = self.cell_center(cell);
+            self.blocked[cell] =
+                GamePlayState::get_wall_contact(walls, center, ENEMY_RADIUS).is_some();
+        }
+    }
+
+    //  Breadth first out from the cell `target` is in, the whole grid every time.
+    pub(super) fn flow_to(&mut self, target: Vec3) {
+        self.distance = [NAV_UNREACHABLE; NAV_CELLS];
+        let Some(start) = self.cell_of(target) else {
+            return;
+        };
+
+        self.distance[start] = 0;
+        self.queue[0] = start as u16;
+        let (mut head, mut tail) = (0, 1);
+        while head < tail {
+            let cell = self.queue[head] as usize;
+            head += 1;
+            for next in neighbours(&self.blocked, cell) {
+                if self.distance[next] == NAV_UNREACHABLE {
+                    self.distance[next] = self.distance[cell] + 1;
+                    self.queue[tail] = next as u16;
+                    tail += 1;
+                }
+            }
+        }
+    }
+
+    //  The middle of the neighbouring cell that's a step closer to the target.
+    //  `None` once in the target's cell, or when there's no way there.
+    pub(super) fn next_waypoint(&self, position: Vec3) -> Option<Vec3> {
+        let cell = self.cell_of(position)?;
+        let (next, distance) = neighbours(&self.blocked, cell)
+            .map(|next| (next, self.distance[next]))
+            .min_by_key(|&(_, distance)| distance)?;
+        (distance < self.distance[cell]).then(|| self.cell_center(next))
+    }
+
+    fn cell_of(&self, position: Vec3) -> Option<usize> {
+        let x = (position[0] - self.origin[0]) / self.cell_size;
+        let z = (position[2] - self.origin[2]) / self.cell_size;
+        if x < 0.0 || z < 0.0 || x as usize >= NAV_GRID_SIZE || z as usize >= NAV_GRID_SIZE {
+            return None;
+        }
+        Some(z as usize * NAV_GRID_SIZE + x as usize)
+    }
+
+    fn cell_center(&self, cell: usize) -> Vec3 {
+        let x = (cell % NAV_GRID_SIZE) as f32 + 0.5;
+        let z = (cell / NAV_GRID_SIZE) as f32 + 0.5;
+        self.origin + Vec3::new(x, 0.0, z) * self.cell_size
+    }
+}
+
+//  Open cells around `cell`, without cutting past the corner of a blocked one.
+fn neighbours(blocked: &[bool; NAV_CELLS], cell: usize) -> impl Iterator<Item = usize> + '_ {
+    let (x, z) = (cell % NAV_GRID_SIZE, cell / NAV_GRID_SIZE);
+    let open = move |x: usize, z: usize| !blocked[z * NAV_GRID_SIZE + x];
+    NAV_NEIGHBOURS.into_iter().filter_map(move |(dx, dz)| {
+        let next_x = x.checked_add_signed(dx).filter(|&x| x < NAV_GRID_SIZE)?;
+        let next_z = z.checked_add_signed(dz).filter(|&z| z < NAV_GRID_SIZE)?;
+        let diagonal = dx != 0 && dz != 0;
+        (open(next_x, next_z) && (!diagonal || open(next_x, z) && open(x, next_z)))
+            .then_some(next_z * NAV_GRID_SIZE + next_x)
+    })
+}
+
+impl GamePlayState {
+    #[inline(always)]
+    pub(super) fn tick_flow_field(&mut self) {
+        if self.ticks.is_multiple_of(NAV_FLOW_FIELD_INTERVAL) {
+            self.flow_field.flow_to(self.player.position);
+        }
+    }
+}
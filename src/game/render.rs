@@ -1,12 +1,12 @@
 use super::*;
 
 impl GamePlayState {
-    pub fn render(&self, fb: &mut Framebuffer) {
+    pub fn render<const W: usize, const H: usize>(&self, fb: &mut Framebuffer<W, H>) {
         fb.clear_color(Color::Gray2);
-        fb.clear_depth(core::f32::MAX);
+        fb.clear_depth(Depth::MAX);
 
         let camera_position = self.player.position;
-        let camera_front = vec_normalize(self.player.direction);
+        let camera_front = self.player.direction.normalize();
 
         let projection = ProjectionData {
             fov_rad: core::f32::consts::FRAC_PI_2,
@@ -20,29 +20,48 @@ impl GamePlayState {
             far: 200.0,
         };
 
+        let light = LightData {
+            direction: Vec3::new(0.6, -1.0, 0.3),
+            ambient: 0.4,
+        };
+
+        //  Fade into the clear color well before the far plane so nothing pops in.
+        let fog = FogData {
+            start: 35.0,
+            end: 65.0,
+            color: Color::Gray2,
+        };
+
         for enemy in &self.enemies {
             let mv = mat4_identity();
-            let mv = mat4_scale(mv, [1.5, -2.0, 1.5]);
+            let mv = mat4_scale(mv, Vec3::new(1.5, -2.0, 1.5));
             let mv = mat4_rotate(
                 mv,
                 (enemy.color as u8 as usize * self.ticks) as f32,
-                [0.0, 1.0, 0.0],
+                Vec3::new(0.0, 1.0, 0.0),
             );
-            let mv = mat4_translate(mv, vec_add_vec(enemy.position, [0.0, 2.0, 0.0]));
+            let mv = mat4_translate(mv, enemy.position + Vec3::new(0.0, 2.0, 0.0));
 
             fb.render_pass(&RenderPass {
                 camera_front,
                 camera_position,
-                triangles: models::person(),
+                mesh: models::person(),
                 model: mv,
                 color: Some(enemy.color),
                 border_color: Some(enemy.color),
                 enable_depth: true,
+                enable_dither: true,
                 projection: Some(projection),
+                light: Some(light),
+                fog: Some(fog),
             })
         }
 
-        for wall in &self.walls {
+        let tan_half_fov = (projection.fov_rad / 2.0).tan() * W as f32 / H as f32;
+        let visible_walls =
+            self.walls
+                .in_view(camera_position, camera_front, tan_half_fov, projection.far);
+        for wall in visible_walls {
             let mv = mat4_identity();
             let mv = mat4_scale(mv, wall.scale);
             let mv = mat4_translate(mv, wall.position);
@@ -50,64 +69,76 @@ impl GamePlayState {
             fb.render_pass(&RenderPass {
                 camera_front,
                 camera_position,
-                triangles: models::cube(),
+                mesh: models::cube(),
                 model: mv,
                 color: Some(Color::Gray1),
                 border_color: Some(Color::Gray0),
                 enable_depth: true,
+                enable_dither: true,
                 projection: Some(projection),
+                light: Some(light),
+                fog: Some(fog),
             })
         }
 
         for bullet in &self.bullets {
             let mv = mat4_identity();
-            let mv = mat4_translate(mv, vec_add_vec(bullet.position, [0.0, 0.25, 0.0]));
-            let mv = mat4_scale(mv, [0.35, 0.35, 0.35]);
+            let mv = mat4_translate(mv, bullet.position + Vec3::new(0.0, 0.25, 0.0));
+            let mv = mat4_scale(mv, Vec3::new(0.35, 0.35, 0.35));
 
             fb.render_pass(&RenderPass {
                 camera_front,
                 camera_position,
-                triangles: models::cube(),
+                mesh: models::cube(),
                 model: mv,
                 color: None,
                 border_color: Some(Color::Yellow6),
                 enable_depth: true,
+                enable_dither: false,
                 projection: Some(projection),
+                light: None,
+                fog: Some(fog),
             })
         }
 
         for explosion in &self.explosions {
             let mv = mat4_identity();
             let mv = mat4_translate(mv, explosion.position);
-            let mv = mat4_scale(mv, vec_mul_scalar([1.0, 1.0, 1.0], explosion.size_scalar));
+            let mv = mat4_scale(mv, Vec3::new(1.0, 1.0, 1.0) * explosion.size_scalar);
 
             fb.render_pass(&RenderPass {
                 camera_front,
                 camera_position,
-                triangles: models::cube(),
+                mesh: models::cube(),
                 model: mv,
                 color: Some(Color::Orange9),
                 border_color: None,
                 enable_depth: true,
+                enable_dither: false,
                 projection: Some(projection),
+                light: None,
+                fog: Some(fog),
             })
         }
 
         if let Some(medkit) = self.medkit {
             let mv = mat4_identity();
-            let mv = mat4_scale(mv, [1.2, 0.3, 1.2]);
-            let mv = mat4_rotate(mv, self.ticks as f32 / 10.0, [0.0, 1.0, 0.0]);
-            let mv = mat4_translate(mv, vec_add_vec(medkit.position, [0.0, 3.0, 0.0]));
+            let mv = mat4_scale(mv, Vec3::new(1.2, 0.3, 1.2));
+            let mv = mat4_rotate(mv, self.ticks as f32 / 10.0, Vec3::new(0.0, 1.0, 0.0));
+            let mv = mat4_translate(mv, medkit.position + Vec3::new(0.0, 3.0, 0.0));
 
             fb.render_pass(&RenderPass {
                 camera_front,
                 camera_position,
-                triangles: models::cube(),
+                mesh: models::cube(),
                 model: mv,
                 color: Some(Color::Red3),
                 border_color: Some(Color::Red3),
                 enable_depth: false,
+                enable_dither: false,
                 projection: Some(far_projection),
+                light: None,
+                fog: None,
             });
         }
     }
@@ -5,39 +5,38 @@ impl GamePlayState {
         let settings = MAP_SETTINGS[self.selected_map];
 
         let north = Wall {
-            scale: [2.0, MAP_WALL_Y, settings.bound],
-            position: [settings.bound, 0.0, 0.0],
+            scale: Vec3::new(2.0, MAP_WALL_Y, settings.bound),
+            position: Vec3::new(settings.bound, 0.0, 0.0),
         };
         let south = Wall {
-            scale: [2.0, MAP_WALL_Y, settings.bound],
-            position: [-settings.bound, 0.0, 0.0],
+            scale: Vec3::new(2.0, MAP_WALL_Y, settings.bound),
+            position: Vec3::new(-settings.bound, 0.0, 0.0),
         };
         let west = Wall {
-            scale: [settings.bound, MAP_WALL_Y, 2.0],
-            position: [0.0, 0.0, -settings.bound],
+            scale: Vec3::new(settings.bound, MAP_WALL_Y, 2.0),
+            position: Vec3::new(0.0, 0.0, -settings.bound),
         };
         let east = Wall {
-            scale: [settings.bound, MAP_WALL_Y, 2.0],
-            position: [0.0, 0.0, settings.bound],
+            scale: Vec3::new(settings.bound, MAP_WALL_Y, 2.0),
+            position: Vec3::new(0.0, 0.0, settings.bound),
         };
 
-        self.walls.push(north);
-        self.walls.push(south);
-        self.walls.push(west);
-        self.walls.push(east);
+        self.walls.list.push(north);
+        self.walls.list.push(south);
+        self.walls.list.push(west);
+        self.walls.list.push(east);
 
         let mut i = 0;
         while i < settings.wall_count {
-            let scale_x = rand_f32() * settings.wall_max_scale as f32 + 3.0;
-            let scale_z = rand_f32() * settings.wall_max_scale as f32 + 3.0;
-            let position_x = rand_f32() * (settings.bound - 2.0) * 2.0 - settings.bound;
-            let position_z = rand_f32() * (settings.bound - 2.0) * 2.0 - settings.bound;
+            let scale_x = self.rand_f32() * settings.wall_max_scale as f32 + 3.0;
+            let scale_z = self.rand_f32() * settings.wall_max_scale as f32 + 3.0;
+            let position_x = self.rand_f32() * (settings.bound - 2.0) * 2.0 - settings.bound;
+            let position_z = self.rand_f32() * (settings.bound - 2.0) * 2.0 - settings.bound;
 
             let valid_wall = self.walls.iter().any(|other| {
-                vec_distance(
-                    [other.position[0], 0.0, other.position[2]],
-                    [position_x, 0.0, position_z],
-                ) < MAP_WALL_TO_WALL_MIN_DISTANCE
+                Vec3::new(other.position[0], 0.0, other.position[2])
+                    .distance(Vec3::new(position_x, 0.0, position_z))
+                    < MAP_WALL_TO_WALL_MIN_DISTANCE
             }) || (position_x.abs() as usize) < settings.wall_max_scale + 4
                 || (position_z.abs() as usize) < settings.wall_max_scale + 4
                 || ((settings.bound - position_x.abs()) as usize) < 18
@@ -48,13 +47,17 @@ impl GamePlayState {
             }
 
             let wall = Wall {
-                scale: [scale_x, MAP_WALL_Y, scale_z],
-                position: [position_x, 0.0, position_z],
+                scale: Vec3::new(scale_x, MAP_WALL_Y, scale_z),
+                position: Vec3::new(position_x, 0.0, position_z),
             };
-            self.walls.push(wall);
+            self.walls.list.push(wall);
 
             i += 1;
         }
+
+        self.walls.build(settings.bound);
+        self.flow_field.build(&self.walls, settings.bound);
+        self.flow_field.flow_to(self.player.position);
     }
 
     pub(super) fn spawn_enemy(&mut self) {
@@ -66,27 +69,28 @@ impl GamePlayState {
         let map_setting = MAP_SETTINGS[self.selected_map];
         let position;
         loop {
-            let position_x = rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
-            let position_z = rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
-            let test_position = [position_x, 0.0, position_z];
-            let dist_to_player = vec_distance(self.player.position, test_position);
-            if (ENEMY_PLAYER_SPAWN_MIN_RADIUS..=ENEMY_PLAYER_SPAWN_MAX_RADIUS)
-                .contains(&dist_to_player)
+            let position_x = self.rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
+            let position_z = self.rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
+            let test_position = Vec3::new(position_x, 0.0, position_z);
+            let dist_to_player = self.player.position.distance(test_position);
+            if Self::get_wall_contact(&self.walls, test_position, ENEMY_RADIUS).is_none()
+                && (ENEMY_PLAYER_SPAWN_MIN_RADIUS..=ENEMY_PLAYER_SPAWN_MAX_RADIUS)
+                    .contains(&dist_to_player)
             {
                 position = test_position;
                 break;
             }
         }
 
-        let random_speed = rand_f32() * 0.4 + 0.1;
-        let random_health = rand_f32() * 100.0 + 40.0;
+        let random_speed = self.rand_f32() * 0.4 + 0.1;
+        let random_health = self.rand_f32() * 100.0 + 40.0;
         //  TODO: Optimize this line.
         let random_color =
-            Color::from(((rand_f32()) * (Color::Orange9 as u8 - 3) as f32).floor() as u8 + 4);
-        let random_dodge = if rand_f32() >= 0.8 {
+            Color::from(((self.rand_f32()) * (Color::Orange9 as u8 - 3) as f32).floor() as u8 + 4);
+        let random_dodge = if self.rand_f32() >= 0.8 {
             0.0
         } else {
-            rand_f32() * ENEMY_MAX_DODGE
+            self.rand_f32() * ENEMY_MAX_DODGE
         };
 
         let enemy = Enemy {
@@ -100,22 +104,26 @@ impl GamePlayState {
     }
 
     pub(super) fn spawn_medkit(&mut self, position: Option<Vec3>) {
-        let position = position.unwrap_or_else(|| {
-            let map_setting = MAP_SETTINGS[self.selected_map];
-
-            loop {
-                let position_x = rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
-                let position_z = rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
-                let position = [position_x, 0.0, position_z];
-                let dist_to_player = vec_distance(self.player.position, position);
-                if Self::get_collision_wall(&self.walls, position).is_none()
-                    && (MEDKIT_PLAYER_SPAWN_MIN_RADIUS..=MEDKIT_PLAYER_SPAWN_MAX_RADIUS)
-                        .contains(&dist_to_player)
-                {
-                    return position;
+        let position = match position {
+            Some(position) => position,
+            None => {
+                let map_setting = MAP_SETTINGS[self.selected_map];
+
+                loop {
+                    let position_x = self.rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
+                    let position_z = self.rand_f32() * map_setting.bound * 2.0 - map_setting.bound;
+                    let position = Vec3::new(position_x, 0.0, position_z);
+                    let dist_to_player = self.player.position.distance(position);
+                    //  Only the point it sits on, pickup range is measured from there.
+                    if Self::get_wall_contact(&self.walls, position, 0.0).is_none()
+                        && (MEDKIT_PLAYER_SPAWN_MIN_RADIUS..=MEDKIT_PLAYER_SPAWN_MAX_RADIUS)
+                            .contains(&dist_to_player)
+                    {
+                        break position;
+                    }
                 }
             }
-        });
+        };
         self.medkit = Some(Medkit { position })
     }
 
@@ -132,6 +140,16 @@ impl GamePlayState {
         let explosion = Explosion {
             position,
             size_scalar: 0.2,
+            max_size: EXPLOSION_MAX_SIZE,
+        };
+        self.explosions.push(explosion);
+    }
+
+    pub(super) fn spawn_bullet_impact(&mut self, position: Vec3) {
+        let explosion = Explosion {
+            position,
+            size_scalar: 0.2,
+            max_size: BULLET_IMPACT_SIZE,
         };
         self.explosions.push(explosion);
     }
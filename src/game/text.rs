@@ -29,5 +29,41 @@ impl GamePlayState {
         .draw(display) else {
             panic!("Failed to draw.");
         };
+
+        //  The seed, with a marker under the digit being changed while still on the title screen.
+        //  Until one is picked it's dashes, and gets filled in once the game starts.
+        const SEED_LABEL: &[u8] = b"SEED ";
+        const SEED_LINE: usize = SEED_LABEL.len() + SEED_DIGITS;
+        let mut seed_text = [b' '; SEED_LINE * 2 + 1];
+        seed_text[..SEED_LABEL.len()].copy_from_slice(SEED_LABEL);
+        seed_text[SEED_LINE] = b'\n';
+        let mut seed = self.seed;
+        for i in (SEED_LABEL.len()..SEED_LINE).rev() {
+            seed_text[i] = match seed {
+                Some(digits) => b'0' + (digits % 10) as u8,
+                None => b'-',
+            };
+            seed = seed.map(|digits| digits / 10);
+        }
+        if let Some(title) = self.title {
+            seed_text[SEED_LINE + 1 + SEED_LABEL.len() + title.cursor] = b'^';
+        }
+        let Ok(seed_text) = core::str::from_utf8(&seed_text) else {
+            panic!("Failed to draw.");
+        };
+        //  Paint the background too, so the last seed doesn't show through.
+        let style = MonoTextStyleBuilder::new()
+            .font(&ascii::FONT_4X6)
+            .text_color(Rgb565::WHITE)
+            .background_color(Rgb565::BLACK)
+            .build();
+        let Ok(_) = Text::new(
+            seed_text,
+            Point::new((SCREEN_WIDTH / 2) as i32 - 22, SCREEN_HEIGHT as i32 - 14),
+            style,
+        )
+        .draw(display) else {
+            panic!("Failed to draw.");
+        };
     }
 }
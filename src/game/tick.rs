@@ -2,11 +2,14 @@ use super::*;
 
 impl GamePlayState {
     pub fn update(&mut self, buttons: Buttons) {
-        if !self.player.dead {
+        if self.title.is_some() {
+            self.tick_title(buttons);
+        } else if !self.player.dead {
             self.ticks += 1;
 
             self.tick_buttons(buttons);
             self.tick_player();
+            self.tick_flow_field();
             self.tick_enemies();
             self.tick_bullets();
             self.tick_medkit();
@@ -24,86 +27,111 @@ impl GamePlayState {
         //  Look in the direction you should be looking
         self.player.direction[0] = self.player.yaw.cos();
         self.player.direction[2] = self.player.yaw.sin();
-        self.player.direction = vec_normalize(self.player.direction);
+        self.player.direction = self.player.direction.normalize();
 
         //  Cap the speed.
-        if vec_length(self.player.velocity) > PLAYER_MAX_VELOCITY {
-            self.player.velocity =
-                vec_mul_scalar(vec_normalize(self.player.velocity), PLAYER_MAX_VELOCITY)
+        if self.player.velocity.length() > PLAYER_MAX_VELOCITY {
+            self.player.velocity = self.player.velocity.normalize() * PLAYER_MAX_VELOCITY;
         }
 
-        //  Friction.
-        self.player.velocity = vec_add_vec(
-            self.player.velocity,
-            vec_mul_scalar(vec_normalize(self.player.velocity), -PLAYER_FRICTION_SCALAR),
-        );
+        //  Friction, which brings the player to a stop rather than pushing them backwards.
+        let speed = self.player.velocity.length();
+        self.player.velocity = match self.player.velocity.try_normalize() {
+            Some(direction) if speed > PLAYER_FRICTION_SCALAR => {
+                self.player.velocity - direction * PLAYER_FRICTION_SCALAR
+            }
+            _ => Vec3::ZERO,
+        };
 
-        //  Move forward unless if there's a wall (then you should bounce!).
-        let next_position = vec_add_vec(self.player.position, self.player.velocity);
-        if let Some(collision_wall) = Self::get_collision_wall(&self.walls, next_position) {
-            //  Over-engineered bouncing.
-            let wall_to_player =
-                vec_normalize(vec_sub_vec(collision_wall.position, self.player.position));
-
-            //  Well, our walls are never rotated anyway ¯\_ (ツ)_/¯.
-            const POSSIBLE_NORMALS: &[Vec3] = &[
-                [-1.0, 0.0, 0.0],
-                [1.0, 0.0, 0.0],
-                [0.0, 0.0, -1.0],
-                [0.0, 0.0, 1.0],
-            ];
-            let mut normal = POSSIBLE_NORMALS[0];
-            let mut min_dist = vec_distance(POSSIBLE_NORMALS[0], wall_to_player);
-            for &n in POSSIBLE_NORMALS {
-                let dist = vec_distance(n, wall_to_player);
-                if dist < min_dist {
-                    min_dist = dist;
-                    normal = n;
+        let next_position = self.player.position + self.player.velocity;
+        match self.player.wall_response {
+            WallResponse::Slide => {
+                self.player.position = Self::slide_against_walls(
+                    &self.walls,
+                    self.player.position,
+                    next_position,
+                    &mut self.player.velocity,
+                    PLAYER_RADIUS,
+                );
+            }
+            WallResponse::Bounce => {
+                //  Move forward unless if there's a wall (then you should bounce!).
+                if let Some(contact) =
+                    Self::get_wall_contact(&self.walls, next_position, PLAYER_RADIUS)
+                {
+                    let normal = contact.normal;
+                    let reflected =
+                        normal * (-2.0 * normal.dot(self.player.velocity)) + self.player.velocity;
+                    self.player.velocity = reflected * PLAYER_WALL_BOUNCE_SCALAR;
+                } else {
+                    self.player.position = next_position;
                 }
             }
-            let reflected = vec_add_vec(
-                vec_mul_scalar(normal, -2.0 * vec_dot(normal, self.player.velocity)),
-                self.player.velocity,
-            );
-            self.player.velocity = vec_mul_scalar(reflected, PLAYER_WALL_BOUNCE_SCALAR);
-        } else {
-            self.player.position = next_position;
         }
     }
 
     #[inline(always)]
     fn tick_enemies(&mut self) {
-        self.enemies.iter_mut().for_each(|enemy| {
+        let positions: SmallVec<[Vec3; 64]> =
+            self.enemies.iter().map(|enemy| enemy.position).collect();
+        let buckets = EnemyBuckets::new(&positions);
+
+        self.enemies.iter_mut().enumerate().for_each(|(i, enemy)| {
             //  Add speed. They will always outrun you. (Not really.)
             enemy.speed += ENEMY_SPEED_INCREMENT_SCALAR;
 
             //  Get closer if enemy isn't already too close.
-            if vec_distance(self.player.position, enemy.position) >= ENEMY_REACH {
-                let direction = vec_normalize(vec_sub_vec(self.player.position, enemy.position));
+            let distance = self.player.position.distance(enemy.position);
+            let mut velocity = if distance >= ENEMY_REACH {
+                //  Walk straight at the player when in sight, otherwise follow the flow field.
+                let target =
+                    if Self::get_wall_hit(&self.walls, enemy.position, self.player.position)
+                        .is_some()
+                    {
+                        self.flow_field
+                            .next_waypoint(enemy.position)
+                            .unwrap_or(self.player.position)
+                    } else {
+                        self.player.position
+                    };
+                //  Don't go through walls. Go around instead.
+                let direction = Self::steer_around_walls(&self.walls, enemy.position, target);
                 //  Adding dodge makes things more interesting.
-                let dodge_direction = vec_mul_scalar(
-                    vec3_cross_product(direction, [0.0, 1.0, 0.0]),
-                    (self.ticks as f32 * enemy.dodge_entropy).sin() * enemy.dodge_entropy,
-                );
-                let next_position =
-                    vec_add_vec(enemy.position, vec_mul_scalar(direction, enemy.speed));
-                let next_position = vec_add_vec(next_position, dodge_direction);
-                //  Don't go through walls. Go around instead. (Or try to anyway.)
-                if Self::get_collision_wall(&self.walls, enemy.position).is_some() {
-                    enemy.position = vec_add_vec(
-                        enemy.position,
-                        vec_mul_scalar(
-                            vec3_cross_product([0.0, 1.0, 0.0], self.player.direction),
-                            enemy.speed,
-                        ),
-                    )
-                } else {
-                    enemy.position = next_position;
-                }
+                let dodge_direction = direction.cross(Vec3::new(0.0, 1.0, 0.0))
+                    * ((self.ticks as f32 * enemy.dodge_entropy).sin() * enemy.dodge_entropy);
+                //  Stop at the ring rather than walking into the player.
+                direction * enemy.speed.min(distance - ENEMY_RING_RADIUS) + dodge_direction
             } else {
                 //  BAM. Attack the player.
                 self.player.health -= ENEMY_DAMAGE;
+                Vec3::ZERO
+            };
+            //  Don't stack up on each other, or keep walking into the ones in the way.
+            let separation = buckets.separation(&positions, i, &mut velocity);
+            velocity += separation;
+
+            //  Crowd around the player on the ring rather than getting pushed onto them.
+            //  Pushed in, only the part along the ring is kept, so nobody cuts across the middle
+            //  and comes out on top of someone else.
+            let mut next_position = enemy.position + velocity;
+            if (next_position - self.player.position).length() < ENEMY_RING_RADIUS {
+                if let Some(outwards) = (enemy.position - self.player.position).try_normalize() {
+                    velocity -= outwards * velocity.dot(outwards).min(0.0);
+                    next_position = enemy.position + velocity;
+                }
+                //  Right on top of the player there's no way out to go by, so pick one.
+                let outwards = (next_position - self.player.position)
+                    .try_normalize()
+                    .unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+                next_position = self.player.position + outwards * ENEMY_RING_RADIUS;
             }
+            enemy.position = Self::slide_against_walls(
+                &self.walls,
+                enemy.position,
+                next_position,
+                &mut velocity,
+                ENEMY_RADIUS,
+            );
 
             //  BAM. Attacked by player.
             if Self::hit_by_bullet(&mut self.bullets, enemy.position) {
@@ -120,20 +148,28 @@ impl GamePlayState {
 
     #[inline(always)]
     fn tick_bullets(&mut self) {
+        let mut impacts: SmallVec<[Vec3; 4]> = smallvec![];
         self.bullets.retain_mut(|bullet| {
-            bullet.position = vec_add_vec(
-                bullet.position,
-                vec_mul_scalar(bullet.direction, BULLET_SPEED),
-            );
-            vec_distance(bullet.position, bullet.origin) <= BULLET_MAX_DISTANCE
+            //  Sweep the whole step so a fast bullet can't skip over a thin wall.
+            let next_position = bullet.position + bullet.direction * BULLET_SPEED;
+            if let Some((_, hit)) = Self::get_wall_hit(&self.walls, bullet.position, next_position)
+            {
+                impacts.push(bullet.position + bullet.direction.normalize_or_zero() * hit.distance);
+                return false;
+            }
+            bullet.position = next_position;
+            bullet.position.distance(bullet.origin) <= BULLET_MAX_DISTANCE
         });
+        for impact in impacts {
+            self.spawn_bullet_impact(impact);
+        }
     }
 
     #[inline(always)]
     fn tick_medkit(&mut self) {
         if let Some(medkit) = self.medkit {
             //  Pick up the medkit if it's close enough.
-            if vec_distance(self.player.position, medkit.position) <= MEDKIT_PICKUP_RANGE {
+            if self.player.position.distance(medkit.position) <= MEDKIT_PICKUP_RANGE {
                 self.player.health += MEDKIT_HEAL_AMOUNT;
                 if self.player.health > PLAYER_MAX_HEALTH {
                     self.player.health = PLAYER_MAX_HEALTH;
@@ -150,7 +186,7 @@ impl GamePlayState {
         self.explosions.retain_mut(|explosion| {
             explosion.size_scalar += EXPLOSION_GROWTH_INCREMENT;
             //  If too big, remove.
-            explosion.size_scalar <= EXPLOSION_MAX_SIZE
+            explosion.size_scalar <= explosion.max_size
         });
     }
 
@@ -174,3 +210,180 @@ impl GamePlayState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enemy(position: Vec3, speed: f32) -> Enemy {
+        Enemy {
+            speed,
+            health: 100.0,
+            color: Color::Red3,
+            position,
+            dodge_entropy: 0.0,
+        }
+    }
+
+    fn assert_finite(v: Vec3) {
+        assert!(v.0.iter().all(|s| s.is_finite()), "{:?}", v);
+    }
+
+    #[test]
+    fn standing_still_stays_put() {
+        let mut state = GamePlayState::with_walls(&[]);
+        for _ in 0..100 {
+            state.tick_player();
+            assert_eq!(state.player.position, Vec3::ZERO);
+            assert_eq!(state.player.velocity, Vec3::ZERO);
+            assert_finite(state.player.direction);
+        }
+    }
+
+    //  Nothing moving, both just overlapping a wall to the right, get pushed out to its side.
+    #[test]
+    fn collisions_at_zero_speed_push_out() {
+        let wall = Wall {
+            scale: Vec3::new(1.0, MAP_WALL_Y, 4.0),
+            position: Vec3::new(1.5, MAP_WALL_Y, 0.0),
+        };
+        let mut state = GamePlayState::with_walls(&[wall]);
+        state.tick_player();
+        assert_finite(state.player.position);
+        assert!((state.player.position[0] + 0.5).abs() < 0.0001);
+        assert_eq!(state.player.velocity, Vec3::ZERO);
+
+        //  Within reach of the player, so attacking rather than moving.
+        let mut state = GamePlayState::with_walls(&[wall]);
+        state.player.position = Vec3::new(-2.0, 0.0, 0.0);
+        state.enemies.push(enemy(Vec3::new(0.0, 0.0, 0.0), 0.0));
+        state.tick_enemies();
+        let position = state.enemies[0].position;
+        assert_finite(position);
+        //  The ring would have it at 0.5, the wall wins.
+        assert!((position[0] + 0.5).abs() < 0.0001, "{:?}", position);
+    }
+
+    //  Running diagonally into a wall to the right, after friction takes 0.01 off the speed.
+    #[test]
+    fn wall_responses() {
+        let wall = Wall {
+            scale: Vec3::new(1.0, MAP_WALL_Y, 4.0),
+            position: Vec3::new(2.0, MAP_WALL_Y, 0.0),
+        };
+        let velocity = Vec3::new(0.4, 0.0, 0.3);
+        let after_friction = velocity * 0.98;
+
+        let mut state = GamePlayState::with_walls(&[wall]);
+        state.player.wall_response = WallResponse::Bounce;
+        state.player.velocity = velocity;
+        state.tick_player();
+        let bounced = Vec3::new(-after_friction[0], 0.0, after_friction[2]);
+        assert_eq!(state.player.position, Vec3::ZERO);
+        assert!(
+            (state.player.velocity - bounced * PLAYER_WALL_BOUNCE_SCALAR).length() < 0.0001,
+            "{:?}",
+            state.player.velocity
+        );
+
+        let mut state = GamePlayState::with_walls(&[wall]);
+        state.player.wall_response = WallResponse::Slide;
+        state.player.velocity = velocity;
+        state.tick_player();
+        let along = Vec3::new(0.0, 0.0, after_friction[2]);
+        assert!((state.player.position - along).length() < 0.0001);
+        assert!((state.player.velocity - along).length() < 0.0001);
+    }
+
+    #[test]
+    fn enemy_on_the_player_is_moved_out_to_the_ring() {
+        let mut state = GamePlayState::with_walls(&[]);
+        state.enemies.push(enemy(Vec3::ZERO, 0.0));
+        state.tick_enemies();
+        let position = state.enemies[0].position;
+        assert_finite(position);
+        assert!(
+            (position.length() - ENEMY_RING_RADIUS).abs() < 0.0001,
+            "{:?}",
+            position
+        );
+        assert_finite(state.player.position);
+    }
+
+    //  Inside a cup opening away from the player, walking straight at them only leads into its
+    //  back wall. The flow field has to lead out of it and around.
+    #[test]
+    fn enemies_find_their_way_out_of_a_cup() {
+        let wall = |x: f32, z: f32, scale_x: f32, scale_z: f32| Wall {
+            scale: Vec3::new(scale_x, MAP_WALL_Y, scale_z),
+            position: Vec3::new(x, 0.0, z),
+        };
+        let mut state = GamePlayState::with_walls(&[
+            wall(0.0, 10.0, 10.0, 1.0),
+            wall(-10.0, 16.0, 1.0, 6.0),
+            wall(10.0, 16.0, 1.0, 6.0),
+        ]);
+        let start = Vec3::new(0.0, 0.0, 16.0);
+        assert!(GamePlayState::get_wall_hit(&state.walls, start, state.player.position).is_some());
+        state.enemies.push(Enemy {
+            dodge_entropy: ENEMY_MAX_DODGE,
+            ..enemy(start, 0.3)
+        });
+
+        //  It takes about 140 ticks.
+        let reached = (0..200).any(|_| {
+            state.ticks += 1;
+            state.tick_flow_field();
+            state.tick_enemies();
+            state.enemies[0].position.distance(state.player.position) < ENEMY_REACH
+        });
+        assert!(reached, "{:?}", state.enemies[0].position);
+    }
+
+    //  More enemies than fit around the player close in from all sides, and however hard the
+    //  ones behind push, no two end up on top of each other.
+    #[test]
+    fn a_crowd_closing_in_keeps_apart() {
+        use nanorand::{Rng, WyRand};
+
+        const ENEMIES: usize = 50;
+        let mut state = GamePlayState::with_walls(&[]);
+        let mut rng = WyRand::new_seed(23);
+        for i in 0..ENEMIES {
+            let angle = i as f32 / ENEMIES as f32 * core::f32::consts::TAU;
+            let distance = 20.0 + rng.generate::<f32>() * 10.0;
+            state.enemies.push(Enemy {
+                dodge_entropy: rng.generate::<f32>() * ENEMY_MAX_DODGE,
+                ..enemy(
+                    Vec3::new(angle.cos(), 0.0, angle.sin()) * distance,
+                    0.1 + rng.generate::<f32>() * 0.4,
+                )
+            });
+        }
+
+        for tick in 0..600 {
+            state.ticks += 1;
+            state.tick_enemies();
+            for (i, a) in state.enemies.iter().enumerate() {
+                for b in &state.enemies[..i] {
+                    let apart = a.position.distance(b.position);
+                    assert!(
+                        apart >= ENEMY_SEPARATION_RADIUS * 0.2,
+                        "tick {}: {:?} and {:?}",
+                        tick,
+                        a.position,
+                        b.position
+                    );
+                }
+            }
+        }
+        let within = |reach: f32| {
+            let enemies = state.enemies.iter();
+            enemies
+                .filter(|enemy| enemy.position.length() < reach)
+                .count()
+        };
+        assert!(within(ENEMY_REACH) >= 5, "{}", within(ENEMY_REACH));
+        assert_eq!(within(ENEMY_REACH * 4.0), ENEMIES);
+    }
+}
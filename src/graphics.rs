@@ -7,310 +7,708 @@ pub struct ProjectionData {
     pub far: f32,
 }
 
-pub struct RenderPass<'triangles> {
+#[derive(Clone, Copy)]
+pub struct LightData {
+    //  The direction light travels in, doesn't need to be normalized.
+    pub direction: Vec3,
+    //  How lit faces turned away from the light still are, from 0.0 to 1.0.
+    pub ambient: f32,
+}
+
+#[derive(Clone, Copy)]
+pub struct FogData {
+    //  View space distances between which fragments fade into `color`.
+    pub start: f32,
+    pub end: f32,
+    pub color: Color,
+}
+
+//  Two palette colors blended by ordered dithering, a `factor` of 0.0 being all `from`.
+#[derive(Clone, Copy)]
+pub struct Dither {
+    pub from: Color,
+    pub to: Color,
+    pub factor: f32,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+impl Dither {
+    #[inline(always)]
+    fn pick(&self, x: usize, y: usize) -> Color {
+        let threshold = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16.0;
+        if self.factor > threshold {
+            self.to
+        } else {
+            self.from
+        }
+    }
+}
+
+impl From<Color> for Dither {
+    fn from(color: Color) -> Self {
+        Dither {
+            from: color,
+            to: color,
+            factor: 0.0,
+        }
+    }
+}
+
+//  Fog resolved against the projected depth rasterized for each fragment.
+#[derive(Clone, Copy)]
+pub struct DepthFog {
+    inv_depth_offset: f32,
+    inv_depth_scale: f32,
+    start: f32,
+    end: f32,
+    color: Color,
+}
+
+impl DepthFog {
+    pub fn new(fog: &FogData, projection: &ProjectionData) -> Self {
+        //  Projected depth is an affine function of 1/z, so undo it to get view space depth back.
+        let k = (projection.far - projection.near) / (projection.far * projection.near);
+        DepthFog {
+            inv_depth_offset: k + 1.0 / projection.near,
+            inv_depth_scale: -k / DEPTH_SCALE,
+            start: fog.start,
+            end: fog.end,
+            color: fog.color,
+        }
+    }
+
+    #[inline(always)]
+    fn apply(&self, color: Dither, x: usize, y: usize, z: f32) -> Dither {
+        let view_depth = 1.0 / (self.inv_depth_offset + self.inv_depth_scale * z);
+        Dither {
+            from: color.pick(x, y),
+            to: self.color,
+            factor: ((view_depth - self.start) / (self.end - self.start)).clamp(0.0, 1.0),
+        }
+    }
+}
+
+pub struct RenderPass<'mesh> {
     pub camera_front: Vec3,
     pub camera_position: Vec3,
-    pub triangles: &'triangles [f32],
+    pub mesh: &'mesh Mesh<'mesh>,
     pub model: Mat4,
     pub color: Option<Color>,
     pub border_color: Option<Color>,
     pub enable_depth: bool,
+    pub enable_dither: bool,
     pub projection: Option<ProjectionData>,
+    pub light: Option<LightData>,
+    pub fog: Option<FogData>,
 }
 
-const FRAMEBUFFER_WIDTH: usize = 30;
-const FRAMEBUFFER_HEIGHT: usize = 30;
+//  Points inside satisfy -w <= x <= w, -w <= y <= w and 0 <= z <= w.
+const CLIP_PLANES: [(Vec4<Real>, Vec4<Real>); 6] = [
+    (
+        Vec4::ZERO,
+        Vec4::new(real(1.0), real(0.0), real(0.0), real(1.0)),
+    ),
+    (
+        Vec4::ZERO,
+        Vec4::new(real(-1.0), real(0.0), real(0.0), real(1.0)),
+    ),
+    (
+        Vec4::ZERO,
+        Vec4::new(real(0.0), real(1.0), real(0.0), real(1.0)),
+    ),
+    (
+        Vec4::ZERO,
+        Vec4::new(real(0.0), real(-1.0), real(0.0), real(1.0)),
+    ),
+    (
+        Vec4::ZERO,
+        Vec4::new(real(0.0), real(0.0), real(1.0), real(0.0)),
+    ),
+    (
+        Vec4::ZERO,
+        Vec4::new(real(0.0), real(0.0), real(-1.0), real(1.0)),
+    ),
+];
+
+//  Projected depth ends up in 1.0..=2.0 once moved onto the screen, spread it out a little for
+//  the depth buffer.
+const DEPTH_SCALE: f32 = 100.0;
+
+//  16.16 fixed point. Both rasterizers snap vertices to it, so they cover the same pixels and
+//  only differ in how they interpolate depth.
+const FIXED_SHIFT: u32 = 16;
+const FIXED_ONE: i64 = 1 << FIXED_SHIFT;
+const FIXED_HALF: i64 = FIXED_ONE / 2;
+
+#[inline(always)]
+fn snap<S: Scalar>(v: Vec3<S>) -> [i64; 2] {
+    [v[0].to_q16_16() as i64, v[1].to_q16_16() as i64]
+}
+
+//  The first pixel whose center is at or past `v`.
+#[inline(always)]
+fn first_center(v: i64) -> i64 {
+    (v - FIXED_HALF + FIXED_ONE - 1) >> FIXED_SHIFT
+}
+
+//  Where the edge from `p` down to `q` crosses the row at `y`, `p` being the upper end so that
+//  a shared edge comes out the same for both triangles.
+#[inline(always)]
+fn edge_x(p: [i64; 2], q: [i64; 2], y: i64) -> i64 {
+    p[0] + ((y - p[1]) * (q[0] - p[0])).div_euclid(q[1] - p[1])
+}
+
+//  What the depth buffer holds. With `fixed-raster` that is the 16.16 value the rasterizer
+//  stepped, so depth tests compare integers.
+#[cfg(not(feature = "fixed-raster"))]
+pub type Depth = f32;
+#[cfg(feature = "fixed-raster")]
+pub type Depth = i32;
+
+trait DepthValue {
+    fn from_float_depth(z: f32) -> Self;
+    fn from_fixed_depth(z: i64) -> Self;
+    fn to_float_depth(self) -> f32;
+}
+
+impl DepthValue for f32 {
+    #[inline(always)]
+    fn from_float_depth(z: f32) -> Self {
+        z
+    }
+
+    #[inline(always)]
+    fn from_fixed_depth(z: i64) -> Self {
+        z as f32 / FIXED_ONE as f32
+    }
+
+    #[inline(always)]
+    fn to_float_depth(self) -> f32 {
+        self
+    }
+}
+
+impl DepthValue for i32 {
+    #[inline(always)]
+    fn from_float_depth(z: f32) -> Self {
+        z.to_q16_16()
+    }
+
+    #[inline(always)]
+    fn from_fixed_depth(z: i64) -> Self {
+        z.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    #[inline(always)]
+    fn to_float_depth(self) -> f32 {
+        self as f32 / FIXED_ONE as f32
+    }
+}
 
-pub struct Framebuffer {
-    colors: [u16; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
-    depths: [f32; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
+pub struct Framebuffer<const W: usize, const H: usize> {
+    colors: [[u16; W]; H],
+    depths: [[Depth; W]; H],
+    //  Render passes skipped entirely since the last `clear_color`.
+    pub culled_passes: usize,
 }
 
-impl Framebuffer {
+impl<const W: usize, const H: usize> Framebuffer<W, H> {
     pub fn new() -> Self {
         Self {
-            colors: [0; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
-            depths: [0.0; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
+            colors: [[0; W]; H],
+            depths: [[Depth::default(); W]; H],
+            culled_passes: 0,
         }
     }
 
     #[inline(always)]
     pub fn clear_color(&mut self, color: Color) {
         let val = Rgb565::from(color).into_storage();
-        self.colors.iter_mut().for_each(|v| *v = val);
+        self.colors.iter_mut().flatten().for_each(|v| *v = val);
+        self.culled_passes = 0;
     }
 
     #[inline(always)]
-    pub fn clear_depth(&mut self, value: f32) {
-        self.depths.iter_mut().for_each(|v| *v = value)
+    pub fn clear_depth(&mut self, value: Depth) {
+        self.depths.iter_mut().flatten().for_each(|v| *v = value)
     }
 
+    //  Fog costs a division, so it is left until the fragment has passed the depth test. It is
+    //  still worked out in floats, so that is the only time a fixed point depth gets converted.
     #[inline(always)]
-    fn put_pixel(&mut self, x: usize, y: usize, z: Option<f32>, color: Color) {
-        if x < FRAMEBUFFER_WIDTH && y < FRAMEBUFFER_HEIGHT {
-            let idx = FRAMEBUFFER_WIDTH * y + x;
-            if let Some(z) = z {
-                if z < self.depths[idx] {
-                    self.depths[idx] = z;
-                    self.colors[idx] = Rgb565::from(color).into_storage();
+    fn put_pixel(
+        &mut self,
+        x: usize,
+        y: usize,
+        z: Depth,
+        enable_depth: bool,
+        color: Dither,
+        fog: Option<DepthFog>,
+    ) {
+        if x < W && y < H {
+            if enable_depth {
+                if z >= self.depths[y][x] {
+                    return;
                 }
-            } else {
-                self.colors[idx] = Rgb565::from(color).into_storage();
+                self.depths[y][x] = z;
             }
+            let color = fog.map_or(color, |fog| fog.apply(color, x, y, z.to_float_depth()));
+            self.colors[y][x] = Rgb565::from(color.pick(x, y)).into_storage();
         }
     }
 
-    #[inline(always)]
-    fn interpolate(i0: f32, d0: f32, i1: f32, d1: f32) -> SmallVec<[f32; 256]> {
-        if i0 == i1 {
-            return smallvec![d0];
+    //  Pixels are covered when their centers fall inside the triangle. A center right on an edge
+    //  goes to the triangle on its right, or below it for a horizontal edge, so triangles sharing
+    //  an edge neither overlap nor leave gaps. Spans come out as a row and its columns.
+    fn triangle_spans(
+        a: [i64; 2],
+        b: [i64; 2],
+        c: [i64; 2],
+        mut span: impl FnMut(usize, core::ops::Range<usize>),
+    ) {
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        if area == 0 {
+            return;
         }
+        let mut sorted = [a, b, c];
+        sorted.sort_unstable_by_key(|v| v[1]);
+        let [a, b, c] = sorted;
 
-        let mut values = smallvec![];
-        let a = (d1 - d0) / (i1 - i0);
-        let mut d = d0;
-        for _ in (i0 as usize)..(i1 as usize) {
-            values.push(d);
-            d += a;
+        for y in first_center(a[1]).max(0)..first_center(c[1]).min(H as i64) {
+            let center = (y << FIXED_SHIFT) + FIXED_HALF;
+            let long = edge_x(a, c, center);
+            let short = if center < b[1] {
+                edge_x(a, b, center)
+            } else {
+                edge_x(b, c, center)
+            };
+            let start = first_center(long.min(short)).max(0);
+            let end = first_center(long.max(short)).min(W as i64);
+            if start < end {
+                span(y as usize, start as usize..end as usize);
+            }
         }
-
-        values
     }
 
-    #[inline(always)]
-    pub fn draw_line(&mut self, mut a: Vec3, mut b: Vec3, color: Color, enable_depth: bool) {
-        let dx = b[0] - a[0];
-        let dy = b[1] - a[1];
+    //  One pixel for each center along the major axis from `a` up to but not including `b`, the
+    //  one the line crosses that center in. Each comes with how far along from `a` its center
+    //  is, over the length of the line, both along the major axis.
+    fn line_pixels(a: [i64; 2], b: [i64; 2], mut pixel: impl FnMut(usize, usize, i64, i64)) {
+        let (major, minor) = if (b[0] - a[0]).abs() > (b[1] - a[1]).abs() {
+            (0, 1)
+        } else {
+            (1, 0)
+        };
+        let (p, q) = if a[major] <= b[major] { (a, b) } else { (b, a) };
+        let limits = [W as i64, H as i64];
 
-        if dx.abs() > dy.abs() {
-            if dx < 0.0 {
-                core::mem::swap(&mut a, &mut b);
+        let length = b[major] - a[major];
+        let start = first_center(p[major]).max(0);
+        let end = first_center(q[major]).min(limits[major]);
+        for i in start..end {
+            let center = (i << FIXED_SHIFT) + FIXED_HALF;
+            let crossing = edge_x([p[minor], p[major]], [q[minor], q[major]], center);
+            let j = crossing >> FIXED_SHIFT;
+            if (0..limits[minor]).contains(&j) {
+                let (x, y) = if major == 0 { (i, j) } else { (j, i) };
+                pixel(x as usize, y as usize, center - a[major], length);
             }
+        }
+    }
 
-            let ys = Self::interpolate(a[0], a[1], b[0], b[1]);
-            for x in (a[0] as usize)..(b[0] as usize) {
-                // Crude estimation of depth.
-                self.put_pixel(
-                    x,
-                    ys[x - a[0] as usize] as usize,
-                    enable_depth.then_some(a[2]),
-                    color,
-                );
-            }
+    //  The Sprig has no FPU, so the `fixed-raster` feature swaps in rasterizers that interpolate
+    //  depth in integers. Coverage is worked out the same way for both, on the 16.16 grid.
+    //  Screen space vertices come in as whatever `Scalar` the pipeline ran on, so with both
+    //  features on nothing is converted to `f32` on the way.
+    #[inline(always)]
+    pub fn draw_line<S: Scalar>(
+        &mut self,
+        a: Vec3<S>,
+        b: Vec3<S>,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        if cfg!(feature = "fixed-raster") {
+            self.draw_line_fixed(a, b, color, enable_depth, fog);
         } else {
-            if dy < 0.0 {
-                core::mem::swap(&mut a, &mut b);
-            }
-
-            let xs = Self::interpolate(a[1], a[0], b[1], b[0]);
-            for y in (a[1] as usize)..(b[1] as usize) {
-                // Crude estimation of depth.
-                self.put_pixel(
-                    xs[y - a[1] as usize] as usize,
-                    y,
-                    enable_depth.then_some(a[2]),
-                    color,
-                );
-            }
+            self.draw_line_float(vec_cast(a), vec_cast(b), color, enable_depth, fog);
         }
     }
 
     #[inline(always)]
-    pub fn draw_triangle(&mut self, a: Vec3, b: Vec3, c: Vec3, color: Color, enable_depth: bool) {
-        self.draw_line(a, b, color, enable_depth);
-        self.draw_line(b, c, color, enable_depth);
-        self.draw_line(a, c, color, enable_depth);
+    fn draw_line_float(
+        &mut self,
+        a: Vec3,
+        b: Vec3,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        Self::line_pixels(snap(a), snap(b), |x, y, along, length| {
+            let z = a[2] + (b[2] - a[2]) * (along as f32 / length as f32);
+            self.put_pixel(x, y, Depth::from_float_depth(z), enable_depth, color, fog);
+        });
     }
 
-    fn fill_triangle(
+    #[inline(always)]
+    pub fn draw_triangle<S: Scalar>(
         &mut self,
-        mut a: Vec3,
-        mut b: Vec3,
-        mut c: Vec3,
-        color: Color,
+        a: Vec3<S>,
+        b: Vec3<S>,
+        c: Vec3<S>,
+        color: Dither,
         enable_depth: bool,
+        fog: Option<DepthFog>,
     ) {
-        if b[1] < a[1] {
-            core::mem::swap(&mut a, &mut b);
-        }
-        if c[1] < a[1] {
-            core::mem::swap(&mut a, &mut c);
-        }
-        if c[1] < b[1] {
-            core::mem::swap(&mut b, &mut c);
+        self.draw_line(a, b, color, enable_depth, fog);
+        self.draw_line(b, c, color, enable_depth, fog);
+        self.draw_line(a, c, color, enable_depth, fog);
+    }
+
+    #[inline(always)]
+    fn fill_triangle<S: Scalar>(
+        &mut self,
+        a: Vec3<S>,
+        b: Vec3<S>,
+        c: Vec3<S>,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        if cfg!(feature = "fixed-raster") {
+            self.fill_triangle_fixed(a, b, c, color, enable_depth, fog);
+        } else {
+            let (a, b, c) = (vec_cast(a), vec_cast(b), vec_cast(c));
+            self.fill_triangle_float(a, b, c, color, enable_depth, fog);
         }
+    }
 
-        let mut x01 = Self::interpolate(a[1], a[0], b[1], b[0]);
-        let mut x12 = Self::interpolate(b[1], b[0], c[1], c[0]);
-        let mut x02 = Self::interpolate(a[1], a[0], c[1], c[0]);
+    fn fill_triangle_float(
+        &mut self,
+        a: Vec3,
+        b: Vec3,
+        c: Vec3,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        //  Projected depth is linear in screen space (it is an affine function of 1/z),
+        //  so the plane through the three vertices is already perspective-correct.
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        let (dz_dx, dz_dy) = if area == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (
+                ((b[2] - a[2]) * (c[1] - a[1]) - (c[2] - a[2]) * (b[1] - a[1])) / area,
+                ((c[2] - a[2]) * (b[0] - a[0]) - (b[2] - a[2]) * (c[0] - a[0])) / area,
+            )
+        };
 
-        x01.pop();
-        x12.pop();
-        x02.pop();
-        let mut x012 = x01.clone();
-        x012.append(&mut x12);
+        Self::triangle_spans(snap(a), snap(b), snap(c), |y, columns| {
+            let row_z = a[2] + dz_dy * (y as f32 + 0.5 - a[1]);
+            for x in columns {
+                let z = row_z + dz_dx * (x as f32 + 0.5 - a[0]);
+                self.put_pixel(x, y, Depth::from_float_depth(z), enable_depth, color, fog);
+            }
+        });
+    }
 
-        let m = x02.len() / 2;
-        if m != 0 && m < x02.len() && m < x012.len() {
-            let (x_left, x_right) = if !x02.is_empty() && !x012.is_empty() && x02[m] < x012[m] {
-                (x02, x012)
-            } else {
-                (x012, x02)
-            };
+    #[inline(always)]
+    fn draw_line_fixed<S: Scalar>(
+        &mut self,
+        a: Vec3<S>,
+        b: Vec3<S>,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        let (za, zb) = (a[2].to_q16_16() as i64, b[2].to_q16_16() as i64);
+        Self::line_pixels(snap(a), snap(b), |x, y, along, length| {
+            let z = za + (zb - za) * along / length;
+            self.put_pixel(x, y, Depth::from_fixed_depth(z), enable_depth, color, fog);
+        });
+    }
 
-            for y in (a[1] as usize)..=(c[1] as usize) {
-                let i = y - a[1] as usize;
-                if i < x_left.len() && i < x_right.len() {
-                    for x in (x_left[i] as usize)..=(x_right[i] as usize) {
-                        // Crude estimation of depth.
-                        self.put_pixel(x, y, enable_depth.then_some(a[2]), color);
-                    }
-                }
-            }
+    //  Same plane as `fill_triangle_float`, with the depth gradients worked out once per
+    //  triangle in 16.16 and then only added along each span.
+    fn fill_triangle_fixed<S: Scalar>(
+        &mut self,
+        a: Vec3<S>,
+        b: Vec3<S>,
+        c: Vec3<S>,
+        color: Dither,
+        enable_depth: bool,
+        fog: Option<DepthFog>,
+    ) {
+        let [a, b, c] = [a, b, c].map(|v| v.0.map(|v| v.to_q16_16() as i64));
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        if area == 0 {
+            return;
         }
+        let gradient = |d: i64| (((d as i128) << FIXED_SHIFT) / area as i128) as i64;
+        let dz_dx = gradient((b[2] - a[2]) * (c[1] - a[1]) - (c[2] - a[2]) * (b[1] - a[1]));
+        let dz_dy = gradient((c[2] - a[2]) * (b[0] - a[0]) - (b[2] - a[2]) * (c[0] - a[0]));
+        let along = |gradient: i64, from: i64, to: i64| {
+            ((gradient as i128 * (to - from) as i128) >> FIXED_SHIFT) as i64
+        };
+
+        let [a2, b2, c2] = [a, b, c].map(|v| [v[0], v[1]]);
+        Self::triangle_spans(a2, b2, c2, |y, columns| {
+            let center_y = ((y as i64) << FIXED_SHIFT) + FIXED_HALF;
+            let center_x = ((columns.start as i64) << FIXED_SHIFT) + FIXED_HALF;
+            let mut z = a[2] + along(dz_dy, a[1], center_y) + along(dz_dx, a[0], center_x);
+            for x in columns {
+                self.put_pixel(x, y, Depth::from_fixed_depth(z), enable_depth, color, fog);
+                z = z.saturating_add(dz_dx);
+            }
+        });
     }
 
+    //  Draw the framebuffer centered on the screen, each pixel blown up into a `scale` square.
     #[inline(always)]
-    pub fn flush<T, E>(&mut self, display: &mut T)
+    pub fn flush<T, E>(&mut self, display: &mut T, scale: usize)
     where
         T: DrawTarget<Color = Rgb565, Error = E>,
     {
-        let raw_image = ImageRawLE::<Rgb565>::new(
-            unsafe {
-                core::slice::from_raw_parts(
-                    self.colors.as_ptr() as *const u8,
-                    self.colors.len() * 2,
-                )
-            },
-            FRAMEBUFFER_WIDTH as u32,
-        );
-        let image = Image::new(
-            &raw_image,
+        let scale = scale.max(1);
+        let area = Rectangle::new(
             Point {
-                x: (SCREEN_WIDTH / 2 - FRAMEBUFFER_WIDTH / 2) as i32,
-                y: (SCREEN_HEIGHT / 2 - FRAMEBUFFER_HEIGHT / 2) as i32,
+                x: (SCREEN_WIDTH.saturating_sub(W * scale) / 2) as i32,
+                y: (SCREEN_HEIGHT.saturating_sub(H * scale) / 2) as i32,
             },
+            Size::new((W * scale) as u32, (H * scale) as u32),
         );
-        let Ok(_) = image.draw(display) else {
+        let colors = &self.colors;
+        let pixels = (0..H * scale).flat_map(move |y| {
+            (0..W * scale).map(move |x| Rgb565::from(RawU16::new(colors[y / scale][x / scale])))
+        });
+        let Ok(_) = display.fill_contiguous(&area, pixels) else {
             panic!("Failed to draw.");
         };
     }
 
     pub fn render_pass(&mut self, pass: &RenderPass) {
-        pass.triangles.chunks(9).for_each(|triangles| {
-            let mut vertices = triangles.chunks(3);
-            let vertex_a: Vec3 = vertices.next().unwrap().try_into().unwrap();
-            let vertex_b: Vec3 = vertices.next().unwrap().try_into().unwrap();
-            let vertex_c: Vec3 = vertices.next().unwrap().try_into().unwrap();
-            let world_vertex_a =
-                vec4_into_vec3(mat4_mul_vec4(pass.model, vec3_into_vec4(vertex_a)));
-            let world_vertex_b =
-                vec4_into_vec3(mat4_mul_vec4(pass.model, vec3_into_vec4(vertex_b)));
-            let world_vertex_c =
-                vec4_into_vec3(mat4_mul_vec4(pass.model, vec3_into_vec4(vertex_c)));
-            let normal = vec_normalize(vec3_cross_product(
-                vec_sub_vec(world_vertex_b, world_vertex_a),
-                vec_sub_vec(world_vertex_c, world_vertex_a),
-            ));
-
-            if vec_dot(normal, vec_sub_vec(world_vertex_a, pass.camera_position)) < 0.0 {
-                let view = mat4_get_look_at(
-                    pass.camera_position,
-                    vec_add_vec(pass.camera_position, pass.camera_front),
-                    [0.0, 1.0, 0.0],
-                );
-                let view_vertex_a =
-                    vec4_into_vec3(mat4_mul_vec4(view, vec3_into_vec4(world_vertex_a)));
-                let view_vertex_b =
-                    vec4_into_vec3(mat4_mul_vec4(view, vec3_into_vec4(world_vertex_b)));
-                let view_vertex_c =
-                    vec4_into_vec3(mat4_mul_vec4(view, vec3_into_vec4(world_vertex_c)));
-
-                let clipped_triangles = if let Some(projection) = &pass.projection {
-                    let test_planes = [
-                        ([0.0, 0.0, projection.near], [0.0, 0.0, 1.0]),
-                        ([0.0, 0.0, projection.far], [0.0, 0.0, -1.0]),
-                    ];
-                    Self::clip_planes((view_vertex_a, view_vertex_b, view_vertex_c), &test_planes)
+        //  The per vertex buffers below only have room for the biggest mesh built from `assets/`.
+        assert!(
+            pass.mesh.vertices.len() <= models::MAX_VERTICES,
+            "mesh has {} vertices, render_pass has room for models::MAX_VERTICES = {}",
+            pass.mesh.vertices.len(),
+            models::MAX_VERTICES
+        );
+
+        let fog = pass
+            .fog
+            .zip(pass.projection)
+            .map(|(fog, projection)| DepthFog::new(&fog, &projection));
+
+        let view = mat4_get_look_at(
+            pass.camera_position,
+            vec_add_vec(pass.camera_position, pass.camera_front),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+
+        let view_projection = pass.projection.map(|projection| {
+            mat4_mul_mat4(
+                view,
+                mat4_get_projection(
+                    H as f32 / W as f32,
+                    projection.fov_rad,
+                    projection.near,
+                    projection.far,
+                ),
+            )
+        });
+
+        if let Some((projection, view_projection)) = pass.projection.zip(view_projection) {
+            if Self::cull_pass(pass, view, view_projection, &projection) {
+                self.culled_passes += 1;
+                return;
+            }
+        }
+
+        //  From here on everything per vertex and per face runs on `Real`, only the matrices
+        //  above are built in `f32`, once per pass.
+        let model = mat4_cast::<f32, Real>(pass.model);
+        let view_projection = view_projection.map(mat4_cast::<f32, Real>);
+        let Some(normal_matrix) = Self::normal_matrix(pass.model) else {
+            return;
+        };
+        let camera_position = vec_cast::<3, f32, Real>(pass.camera_position);
+        let light = pass.light.map(|light| {
+            (
+                vec_cast::<3, f32, Real>(vec_normalize(light.direction)),
+                Real::from_f32(light.ambient),
+            )
+        });
+        let screen_scale = Vec3::new(
+            Real::from_f32((W - 1) as f32 / 2.0),
+            Real::from_f32((H - 1) as f32 / 2.0),
+            Real::from_f32(DEPTH_SCALE),
+        );
+
+        //  Shared vertices only need to be transformed once.
+        //  Sized for the biggest mesh in `models`, so nothing spills onto the heap.
+        let mut world_vertices = [Vec3::<Real>::ZERO; models::MAX_VERTICES];
+        let mut clip_vertices = [Vec4::<Real>::ZERO; models::MAX_VERTICES];
+        for (i, &vertex) in pass.mesh.vertices.iter().enumerate() {
+            let vertex = vec3_into_vec4(vertex);
+            let world_vertex = mat4_mul_vec4(model, vertex);
+            world_vertices[i] = vec4_into_vec3(world_vertex);
+            clip_vertices[i] = if let Some(view_projection) = view_projection {
+                mat4_mul_vec4(view_projection, world_vertex)
+            } else {
+                vertex
+            };
+        }
+
+        //  Everything is clipped in clip space, before the perspective divide.
+        //  Near and far only make sense with a projection.
+        let test_planes = if pass.projection.is_some() {
+            &CLIP_PLANES[..]
+        } else {
+            &CLIP_PLANES[..4]
+        };
+
+        for (face, indices) in pass.mesh.indices.chunks(3).enumerate() {
+            let a = indices[0] as usize;
+            let b = indices[1] as usize;
+            let c = indices[2] as usize;
+            let normal = vec_normalize(vec4_into_vec3(mat4_mul_vec4(
+                normal_matrix,
+                pass.mesh.face_normals[face].extend(Real::ZERO),
+            )));
+
+            if vec_dot(normal, vec_sub_vec(world_vertices[a], camera_position)) < Real::ZERO {
+                let color = pass
+                    .color
+                    .map(|color| pass.mesh.face_colors.map_or(color, |colors| colors[face]));
+                let (color, border_color) = if let Some((direction, ambient)) = light {
+                    let diffuse = vec_dot(normal, direction);
+                    let diffuse = if diffuse < Real::ZERO {
+                        diffuse
+                    } else {
+                        Real::ZERO
+                    };
+                    let intensity = ambient + -diffuse * (Real::ONE - ambient);
+                    //  Picking the shades needs the fraction anyway, one conversion per face.
+                    let level = intensity.to_f32() * (SHADE_LEVELS - 1) as f32;
+                    let factor = if pass.enable_dither {
+                        level.fract()
+                    } else {
+                        level.fract().round()
+                    };
+                    let shade = |color: Color| Dither {
+                        from: color.shade(level as usize),
+                        to: color.shade(level as usize + 1),
+                        factor,
+                    };
+                    (color.map(shade), pass.border_color.map(shade))
                 } else {
-                    smallvec![(view_vertex_a, view_vertex_b, view_vertex_c)]
+                    (color.map(Dither::from), pass.border_color.map(Dither::from))
                 };
 
+                let clipped_triangles = Self::clip_planes(
+                    (clip_vertices[a], clip_vertices[b], clip_vertices[c]),
+                    test_planes,
+                );
+
                 clipped_triangles.iter().for_each(|triangle| {
-                    let (vertex_a, vertex_b, vertex_c) = if let Some(projection) = &pass.projection
-                    {
-                        let projection = mat4_get_projection(
-                            1.0,
-                            projection.fov_rad,
-                            projection.near,
-                            projection.far,
-                        );
-                        let projected_vertex_a =
-                            mat4_mul_vec4(projection, vec3_into_vec4(triangle.0));
-                        let projected_vertex_b =
-                            mat4_mul_vec4(projection, vec3_into_vec4(triangle.1));
-                        let projected_vertex_c =
-                            mat4_mul_vec4(projection, vec3_into_vec4(triangle.2));
-
-                        (
-                            vec4_into_vec3(vec4_scale_with_w(projected_vertex_a)),
-                            vec4_into_vec3(vec4_scale_with_w(projected_vertex_b)),
-                            vec4_into_vec3(vec4_scale_with_w(projected_vertex_c)),
-                        )
-                    } else {
-                        (vertex_a, vertex_b, vertex_c)
+                    let to_screen = |vertex: Vec4<Real>| {
+                        let mut vertex =
+                            vec_add_scalar(vec4_into_vec3(vec4_scale_with_w(vertex)), Real::ONE);
+                        for axis in 0..3 {
+                            vertex[axis] *= screen_scale[axis];
+                        }
+                        vertex
                     };
+                    let a = to_screen(triangle.0);
+                    let b = to_screen(triangle.1);
+                    let c = to_screen(triangle.2);
 
-                    let mut vertex_a = vec_add_scalar(vertex_a, 1.0);
-                    let mut vertex_b = vec_add_scalar(vertex_b, 1.0);
-                    let mut vertex_c = vec_add_scalar(vertex_c, 1.0);
-                    vertex_a[0] *= (FRAMEBUFFER_WIDTH / 2) as f32;
-                    vertex_b[0] *= (FRAMEBUFFER_WIDTH / 2) as f32;
-                    vertex_c[0] *= (FRAMEBUFFER_WIDTH / 2) as f32;
-
-                    vertex_a[1] *= (FRAMEBUFFER_HEIGHT / 2) as f32;
-                    vertex_b[1] *= (FRAMEBUFFER_HEIGHT / 2) as f32;
-                    vertex_c[1] *= (FRAMEBUFFER_HEIGHT / 2) as f32;
-
-                    vertex_a[2] *= 100.0;
-                    vertex_b[2] *= 100.0;
-                    vertex_c[2] *= 100.0;
-
-                    let test_planes = [
-                        ([0.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
-                        (
-                            [0.0, FRAMEBUFFER_HEIGHT as f32 - 1.0, 0.0],
-                            [0.0, -1.0, 0.0],
-                        ),
-                        ([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]),
-                        ([FRAMEBUFFER_WIDTH as f32 - 1.0, 0.0, 0.0], [-1.0, 0.0, 0.0]),
-                    ];
-
-                    let final_triangles =
-                        Self::clip_planes((vertex_a, vertex_b, vertex_c), &test_planes);
-
-                    final_triangles.iter().for_each(|&(a, b, c)| {
-                        if let Some(border_color) = pass.border_color {
-                            self.draw_triangle(a, b, c, border_color, pass.enable_depth);
-                        }
-                        if let Some(color) = pass.color {
-                            self.fill_triangle(a, b, c, color, pass.enable_depth);
-                        }
-                    });
+                    if let Some(border_color) = border_color {
+                        self.draw_triangle(a, b, c, border_color, pass.enable_depth, fog);
+                    }
+                    if let Some(color) = color {
+                        self.fill_triangle(a, b, c, color, pass.enable_depth, fog);
+                    }
                 });
             }
-        });
+        }
+    }
+
+    //  Takes model space face normals into world space. This is the cofactor matrix of the
+    //  model, which keeps normals at right angles to their faces under uneven scales and flips
+    //  them along with a mirroring one. Only directions matter, so it is scaled down to keep
+    //  `Real` well inside its range. `None` when the model flattens everything.
+    fn normal_matrix(model: Mat4) -> Option<Mat4<Real>> {
+        let [r0, r1, r2] = [0, 1, 2].map(|row| vec4_into_vec3(model[row]));
+        let sign = vec_dot(r0, vec3_cross_product(r1, r2)).signum();
+        let normals = mat4_transpose(mat4_inverse(model)?);
+        let largest = (0..3)
+            .flat_map(|row| (0..3).map(move |column| normals[row][column].abs()))
+            .fold(0.0, f32::max);
+        Some(mat4_cast(normals.map(|v| v * sign / largest)))
+    }
+
+    //  Test the bounding sphere of the mesh against the view frustum, then its bounds for when the
+    //  sphere is a poor fit, like on long thin walls.
+    fn cull_pass(
+        pass: &RenderPass,
+        view: Mat4,
+        view_projection: Mat4,
+        projection: &ProjectionData,
+    ) -> bool {
+        let world_center = mat4_mul_vec4(pass.model, vec3_into_vec4(pass.mesh.sphere_center));
+        let [x, y, z] = vec4_into_vec3(mat4_mul_vec4(view, world_center)).0;
+        //  Scaling the model scales the sphere by its largest axis.
+        let scale = (0..3)
+            .map(|axis| vec_length(vec4_into_vec3(pass.model[axis])))
+            .fold(0.0, f32::max);
+        let radius = pass.mesh.sphere_radius * scale;
+
+        if z + radius < projection.near || z - radius > projection.far {
+            return true;
+        }
+
+        //  The side planes go through the camera, so only their normals are needed.
+        let scale_y = 1.0 / (projection.fov_rad / 2.0).tan();
+        let scale_x = scale_y * H as f32 / W as f32;
+        let length_x = (scale_x * scale_x + 1.0).sqrt();
+        let length_y = (scale_y * scale_y + 1.0).sqrt();
+        if (scale_x * x.abs() - z) / length_x > radius
+            || (scale_y * y.abs() - z) / length_y > radius
+        {
+            return true;
+        }
+
+        //  Bring the clip planes back into model space, where the bounds are axis aligned, and
+        //  check whether the corner furthest along any of them is still behind it.
+        let planes = mat4_transpose(mat4_mul_mat4(pass.model, view_projection));
+        CLIP_PLANES.iter().any(|&(_, normal)| {
+            let plane = mat4_mul_vec4(planes, vec_cast(normal));
+            let furthest = |axis: usize| {
+                if plane[axis] >= 0.0 {
+                    pass.mesh.bounds_max[axis]
+                } else {
+                    pass.mesh.bounds_min[axis]
+                }
+            };
+            let corner = Vec3::new(furthest(0), furthest(1), furthest(2));
+            vec_dot(vec4_into_vec3(plane), corner) + plane[3] < 0.0
+        })
     }
 
     #[inline(always)]
     fn clip_planes(
-        vertices: (Vec3, Vec3, Vec3),
-        test_planes: &[(Vec3, Vec3)],
-    ) -> SmallVec<[(Vec3, Vec3, Vec3); 2]> {
-        let mut final_triangles: SmallVec<[(Vec3, Vec3, Vec3); 2]> = smallvec![vertices];
+        vertices: Triangle<4, Real>,
+        test_planes: &[(Vec4<Real>, Vec4<Real>)],
+    ) -> SmallVec<[Triangle<4, Real>; 8]> {
+        let mut final_triangles: SmallVec<[Triangle<4, Real>; 8]> = smallvec![vertices];
 
         for plane in test_planes {
-            let mut passed: SmallVec<[(Vec3, Vec3, Vec3); 2]> = smallvec![];
+            let mut passed: SmallVec<[Triangle<4, Real>; 8]> = smallvec![];
             for t in final_triangles {
                 passed.append(&mut triangle_clip_plane(plane.0, plane.1, t));
             }
@@ -319,3 +717,324 @@ impl Framebuffer {
         final_triangles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const W: usize = 40;
+    const H: usize = 32;
+
+    fn storage(color: Color) -> u16 {
+        Rgb565::from(color).into_storage()
+    }
+
+    fn cleared() -> Framebuffer<W, H> {
+        let mut fb = Framebuffer::new();
+        fb.clear_color(Color::Gray0);
+        fb.clear_depth(Depth::MAX);
+        fb
+    }
+
+    //  Both cover the same pixels, one getting deeper to the right and the other shallower, so
+    //  they cross halfway along x.
+    #[test]
+    fn interpenetrating_triangles_resolve_per_pixel() {
+        let deeper_right = [
+            Vec3::new(0.0, 0.0, 10.0),
+            Vec3::new(39.0, 0.0, 49.0),
+            Vec3::new(20.0, 31.0, 30.0),
+        ];
+        let deeper_left = [
+            Vec3::new(0.0, 0.0, 50.0),
+            Vec3::new(39.0, 0.0, 11.0),
+            Vec3::new(20.0, 31.0, 30.0),
+        ];
+
+        for reversed in [false, true] {
+            let mut fb = cleared();
+            let mut passes = [(deeper_right, Color::Red3), (deeper_left, Color::Blue7)];
+            if reversed {
+                passes.reverse();
+            }
+            for ([a, b, c], color) in passes {
+                fb.fill_triangle(a, b, c, Dither::from(color), true, None);
+            }
+
+            let (mut left, mut right) = (0, 0);
+            for y in 0..H {
+                for x in 0..W {
+                    let color = fb.colors[y][x];
+                    if color == storage(Color::Gray0) || (19..=21).contains(&x) {
+                        continue;
+                    }
+                    if x < 19 {
+                        assert_eq!(color, storage(Color::Red3), "({}, {})", x, y);
+                        left += 1;
+                    } else {
+                        assert_eq!(color, storage(Color::Blue7), "({}, {})", x, y);
+                        right += 1;
+                    }
+                }
+            }
+            assert!(left > 100 && right > 100, "{} {}", left, right);
+        }
+    }
+
+    #[test]
+    fn dithering_follows_the_bayer_matrix() {
+        for factor in [0.0, 0.25, 0.5, 0.8, 1.0] {
+            let mut fb = cleared();
+            let dither = Dither {
+                from: Color::Red3,
+                to: Color::Blue7,
+                factor,
+            };
+            fb.fill_triangle(
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(39.0, 0.0, 1.0),
+                Vec3::new(0.0, 31.0, 1.0),
+                dither,
+                false,
+                None,
+            );
+
+            //  The top left 16x16 is well inside the triangle, every 4x4 tile of it should have
+            //  `factor` of its pixels in `to`, where the matrix puts them.
+            let expected_to = (0..16)
+                .filter(|i| factor > (*i as f32 + 0.5) / 16.0)
+                .count();
+            for tile_y in 0..4 {
+                for tile_x in 0..4 {
+                    let mut to = 0;
+                    for y in tile_y * 4..tile_y * 4 + 4 {
+                        for x in tile_x * 4..tile_x * 4 + 4 {
+                            let threshold = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16.0;
+                            let expected = if factor > threshold {
+                                to += 1;
+                                Color::Blue7
+                            } else {
+                                Color::Red3
+                            };
+                            assert_eq!(fb.colors[y][x], storage(expected), "({}, {})", x, y);
+                        }
+                    }
+                    assert_eq!(to, expected_to);
+                }
+            }
+        }
+    }
+
+    //  A cube of `scale` at `position`, seen from the origin looking down +z.
+    fn render_cube(fb: &mut Framebuffer<W, H>, position: Vec3, scale: Vec3) {
+        let model = mat4_translate(mat4_scale(mat4_identity(), scale), position);
+        fb.render_pass(&RenderPass {
+            camera_front: Vec3::new(0.0, 0.0, 1.0),
+            camera_position: Vec3::ZERO,
+            mesh: models::cube(),
+            model,
+            color: Some(Color::Red3),
+            border_color: None,
+            enable_depth: true,
+            enable_dither: false,
+            projection: Some(ProjectionData {
+                fov_rad: core::f32::consts::FRAC_PI_2,
+                near: 0.1,
+                far: 70.0,
+            }),
+            light: None,
+            fog: None,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "render_pass has room for models::MAX_VERTICES")]
+    fn meshes_past_max_vertices_are_refused() {
+        let cube = models::cube();
+        let vertices = vec![Vec3::<Real>::ZERO; models::MAX_VERTICES + 1];
+        let mesh = Mesh {
+            vertices: &vertices,
+            ..*cube
+        };
+        let mut fb = cleared();
+        fb.render_pass(&RenderPass {
+            camera_front: Vec3::new(0.0, 0.0, 1.0),
+            camera_position: Vec3::ZERO,
+            mesh: &mesh,
+            model: mat4_identity(),
+            color: Some(Color::Red3),
+            border_color: None,
+            enable_depth: true,
+            enable_dither: false,
+            projection: None,
+            light: None,
+            fog: None,
+        });
+    }
+
+    fn drawn(fb: &Framebuffer<W, H>) -> usize {
+        let clear = storage(Color::Gray0);
+        fb.colors
+            .iter()
+            .flatten()
+            .filter(|&&color| color != clear)
+            .count()
+    }
+
+    #[test]
+    fn passes_outside_the_frustum_are_culled() {
+        let one = Vec3::new(1.0, 1.0, 1.0);
+        let outside = [
+            //  Behind the near plane.
+            (Vec3::new(0.0, 0.0, -5.0), one),
+            //  Past the far plane.
+            (Vec3::new(0.0, 0.0, 200.0), one),
+            //  Off to the left, right, top and bottom.
+            (Vec3::new(-60.0, 0.0, 10.0), one),
+            (Vec3::new(60.0, 0.0, 10.0), one),
+            (Vec3::new(0.0, 60.0, 10.0), one),
+            (Vec3::new(0.0, -60.0, 10.0), one),
+            //  A long thin wall behind the camera whose bounding sphere still reaches in front.
+            (Vec3::new(-22.0, 0.0, -2.0), Vec3::new(20.0, 1.0, 0.5)),
+        ];
+        for (position, scale) in outside {
+            let mut fb = cleared();
+            render_cube(&mut fb, position, scale);
+            assert_eq!(fb.culled_passes, 1, "{:?}", position);
+            assert_eq!(drawn(&fb), 0, "{:?}", position);
+        }
+    }
+
+    #[test]
+    fn passes_straddling_the_frustum_are_drawn() {
+        let one = Vec3::new(1.0, 1.0, 1.0);
+        let straddling = [
+            //  Across the near plane, the far plane and the right side.
+            (Vec3::new(1.5, 0.0, 1.05), one),
+            (Vec3::new(0.0, 0.0, 70.0), Vec3::new(30.0, 30.0, 5.0)),
+            (Vec3::new(11.5, 0.0, 10.0), one),
+            //  Only a corner of a long wall pokes into view.
+            (Vec3::new(-20.0, 0.0, 3.0), Vec3::new(20.0, 1.0, 0.5)),
+        ];
+        for (position, scale) in straddling {
+            let mut fb = cleared();
+            render_cube(&mut fb, position, scale);
+            assert_eq!(fb.culled_passes, 0, "{:?}", position);
+            assert!(drawn(&fb) > 0, "{:?}", position);
+        }
+    }
+
+    //  Split along the diagonal, the two halves of a rectangle cover exactly the pixel centers
+    //  inside it, with none left out or drawn twice.
+    #[test]
+    fn shared_edges_cover_each_pixel_once() {
+        let [a, b, c, d] = [
+            Vec3::new(0.3, 0.7, 100.0),
+            Vec3::new(20.3, 0.7, 100.0),
+            Vec3::new(20.3, 10.7, 100.0),
+            Vec3::new(0.3, 10.7, 100.0),
+        ];
+        let mut upper = cleared();
+        let mut lower = cleared();
+        let mut both = cleared();
+        upper.fill_triangle(a, b, c, Dither::from(Color::Red3), false, None);
+        lower.fill_triangle(a, c, d, Dither::from(Color::Red3), false, None);
+        both.fill_triangle(a, b, c, Dither::from(Color::Red3), false, None);
+        both.fill_triangle(a, c, d, Dither::from(Color::Red3), false, None);
+        assert_eq!(drawn(&upper) + drawn(&lower), 20 * 10);
+        assert_eq!(drawn(&both), 20 * 10);
+    }
+
+    //  Both rasterizers snap to the same grid, so they have to draw exactly the same pixels.
+    //  Only depth is interpolated differently, and has to agree to within 0.01.
+    fn assert_same_pixels(float: &Framebuffer<W, H>, fixed: &Framebuffer<W, H>, shape: &str) {
+        assert!(float.colors == fixed.colors, "{}", shape);
+        for (float_z, fixed_z) in float
+            .depths
+            .iter()
+            .flatten()
+            .zip(fixed.depths.iter().flatten())
+        {
+            assert_eq!(*float_z == Depth::MAX, *fixed_z == Depth::MAX, "{}", shape);
+            let error = (float_z.to_float_depth() - fixed_z.to_float_depth()).abs();
+            assert!(*float_z == Depth::MAX || error <= 0.01, "{}", shape);
+        }
+    }
+
+    #[test]
+    fn fixed_rasterizer_matches_float() {
+        use nanorand::{Rng, WyRand};
+
+        let mut rng = WyRand::new_seed(13);
+        let mut vertex = || {
+            let mut coord = |min: f32, max: f32| min + rng.generate::<f32>() * (max - min);
+            Vec3::new(
+                coord(-5.0, W as f32 + 5.0),
+                coord(-5.0, H as f32 + 5.0),
+                coord(100.0, 200.0),
+            )
+        };
+
+        for _ in 0..2000 {
+            let [a, b, c] = [vertex(), vertex(), vertex()];
+            let color = Dither::from(Color::Red3);
+
+            let mut float = cleared();
+            let mut fixed = cleared();
+            float.fill_triangle_float(a, b, c, color, true, None);
+            fixed.fill_triangle_fixed(a, b, c, color, true, None);
+            assert_same_pixels(&float, &fixed, &format!("{:?} {:?} {:?}", a, b, c));
+
+            let mut float = cleared();
+            let mut fixed = cleared();
+            float.draw_line_float(a, b, color, true, None);
+            fixed.draw_line_fixed(a, b, color, true, None);
+            assert_same_pixels(&float, &fixed, &format!("{:?} {:?}", a, b));
+        }
+    }
+
+    //  Precomputed normals taken through `normal_matrix` have to point the same way as normals
+    //  worked out from the transformed triangles, mirrored and squashed models included.
+    #[test]
+    fn face_normals_follow_the_model() {
+        let mesh = models::cube();
+        for scale in [
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(20.0, 1.0, 0.5),
+            Vec3::new(-2.0, 3.0, 1.0),
+            Vec3::new(-1.0, -1.0, -4.0),
+        ] {
+            let model = mat4_translate(
+                mat4_rotate(
+                    mat4_scale(mat4_identity(), scale),
+                    0.7,
+                    vec_normalize(Vec3::new(1.0, 2.0, 0.5)),
+                ),
+                Vec3::new(300.0, -2.0, 150.0),
+            );
+            let normal_matrix = Framebuffer::<W, H>::normal_matrix(model).unwrap();
+            let vertices = mesh
+                .vertices
+                .iter()
+                .map(|&vertex| mat4_mul_vec4(model, vec3_into_vec4(vec_cast(vertex))).truncate())
+                .collect::<std::vec::Vec<_>>();
+
+            for (face, indices) in mesh.indices.chunks(3).enumerate() {
+                let [a, b, c] = [0, 1, 2].map(|i| vertices[indices[i] as usize]);
+                let expected =
+                    vec_normalize(vec3_cross_product(vec_sub_vec(b, a), vec_sub_vec(c, a)));
+                let normal = vec_cast::<3, Real, f32>(vec_normalize(vec4_into_vec3(
+                    mat4_mul_vec4(normal_matrix, mesh.face_normals[face].extend(Real::ZERO)),
+                )));
+                assert!(
+                    vec_dot(normal, expected) > 0.999,
+                    "{:?} face {}: {:?} {:?}",
+                    scale.0,
+                    face,
+                    normal.0,
+                    expected.0
+                );
+            }
+        }
+    }
+}
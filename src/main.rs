@@ -3,10 +3,10 @@
 
 use embedded_graphics::{
     draw_target::DrawTarget,
-    image::{Image, ImageRawLE},
-    mono_font::{ascii, MonoTextStyle},
-    pixelcolor::{Rgb565, Rgb888},
+    mono_font::{ascii, MonoTextStyle, MonoTextStyleBuilder},
+    pixelcolor::{raw::RawU16, Rgb565, Rgb888},
     prelude::*,
+    primitives::Rectangle,
     text::Text,
 };
 use smallvec::{smallvec, SmallVec};
@@ -20,22 +20,72 @@ mod game;
 mod graphics;
 mod math;
 mod models;
+mod palette;
+mod scalar;
 
-use color::Color;
-use game::GamePlayState;
-use graphics::{Framebuffer, ProjectionData, RenderPass};
+use color::{Color, SHADE_LEVELS};
+use game::{GamePlayState, SEED_LIMIT};
+use graphics::{Depth, FogData, Framebuffer, LightData, ProjectionData, RenderPass};
 use math::{
-    mat4_get_look_at, mat4_get_projection, mat4_identity, mat4_mul_mat4, mat4_mul_vec4,
-    mat4_rotate, mat4_scale, mat4_translate, triangle_clip_plane, vec3_cross_product,
-    vec3_into_vec4, vec4_into_vec3, vec4_scale_with_w, vec_add_scalar, vec_add_vec, vec_distance,
-    vec_dot, vec_length, vec_mul_scalar, vec_normalize, vec_sub_vec, Mat4, Vec3, Vec4,
+    mat4_cast, mat4_get_look_at, mat4_get_projection, mat4_identity, mat4_inverse, mat4_mul_mat4,
+    mat4_mul_vec4, mat4_rotate, mat4_scale, mat4_translate, mat4_transpose, triangle_clip_plane,
+    vec3_cross_product, vec3_into_vec4, vec4_into_vec3, vec4_scale_with_w, vec_add_scalar,
+    vec_add_vec, vec_cast, vec_dot, vec_length, vec_normalize, vec_sub_vec, Aabb, Contact, Hit,
+    Mat4, Sphere, Triangle, Vec3, Vec4,
 };
+use models::Mesh;
+use palette::PALETTE;
+use scalar::{real, Real, Scalar};
 
 const SCREEN_WIDTH: usize = 160;
 const SCREEN_HEIGHT: usize = 128;
 
+//  Trade resolution for frame rate per build, for example:
+//  `ROOM_FRAMEBUFFER_WIDTH=40 ROOM_FRAMEBUFFER_HEIGHT=32 ROOM_FRAMEBUFFER_SCALE=4 cargo run-sprig`.
+const FRAMEBUFFER_WIDTH: usize = env_usize(option_env!("ROOM_FRAMEBUFFER_WIDTH"), 30);
+const FRAMEBUFFER_HEIGHT: usize = env_usize(option_env!("ROOM_FRAMEBUFFER_HEIGHT"), 30);
+const FRAMEBUFFER_SCALE: usize = env_usize(option_env!("ROOM_FRAMEBUFFER_SCALE"), 1);
+
+//  Pick the seed per build rather than on the title screen, for example `ROOM_SEED=1234`.
+const SEED: Option<u32> = match option_env!("ROOM_SEED") {
+    Some(value) => Some(env_u32(value)),
+    None => None,
+};
+const _: () = if let Some(seed) = SEED {
+    assert!(
+        seed < SEED_LIMIT,
+        "ROOM_SEED has more digits than the title screen shows."
+    );
+};
+
+const fn env_usize(value: Option<&str>, default: usize) -> usize {
+    match value {
+        Some(value) => env_u32(value) as usize,
+        None => default,
+    }
+}
+
+const fn env_u32(value: &str) -> u32 {
+    let bytes = value.as_bytes();
+    assert!(!bytes.is_empty(), "Expected an unsigned integer.");
+    let mut result: u32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "Expected an unsigned integer.");
+        let Some(shifted) = result.checked_mul(10) else {
+            panic!("Too big for a u32.");
+        };
+        let Some(added) = shifted.checked_add((bytes[i] - b'0') as u32) else {
+            panic!("Too big for a u32.");
+        };
+        result = added;
+        i += 1;
+    }
+    result
+}
+
 struct Game {
-    framebuffer: Framebuffer,
+    framebuffer: Framebuffer<FRAMEBUFFER_WIDTH, FRAMEBUFFER_HEIGHT>,
     game: GamePlayState,
 }
 
@@ -43,14 +93,17 @@ impl Game {
     pub fn new() -> Self {
         Self {
             framebuffer: Framebuffer::new(),
-            game: GamePlayState::new(),
+            game: match SEED {
+                Some(seed) => GamePlayState::with_seed(seed),
+                None => GamePlayState::new(),
+            },
         }
     }
 }
 
 impl App for Game {
     fn init(&mut self) -> AppResult {
-        self.game.init();
+        //  The game starts from the title screen, once the seed is picked.
         Ok(())
     }
 
@@ -66,9 +119,13 @@ impl App for Game {
         if self.game.ticks <= 2 {
             self.game.init_text(display);
         }
+        //  The title screen is only text, a full screen framebuffer would paint over it.
+        if self.game.on_title() {
+            return Ok(());
+        }
 
         self.game.render(&mut self.framebuffer);
-        self.framebuffer.flush(display);
+        self.framebuffer.flush(display, FRAMEBUFFER_SCALE);
         Ok(())
     }
 }
@@ -1,22 +1,630 @@
 use super::*;
+use core::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
 
-pub type Vec<const V: usize> = [f32; V];
-pub type Vec3 = Vec<3>;
-pub type Vec4 = Vec<4>;
-pub type Mat4 = [Vec4; 4];
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec<const V: usize, S = f32>(pub [S; V]);
+pub type Vec3<S = f32> = Vec<3, S>;
+pub type Vec4<S = f32> = Vec<4, S>;
+pub type Triangle<const V: usize, S = f32> = (Vec<V, S>, Vec<V, S>, Vec<V, S>);
+
+//  Rows of a matrix meant for row vectors, translation lives in the last row.
+//  `mat * vec` transforms `vec` and `a * b` applies `b` first, so `(a * b) * v == a * (b * v)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4<S = f32>(pub [Vec4<S>; 4]);
+
+impl<S> Vec<3, S> {
+    #[inline(always)]
+    pub const fn new(x: S, y: S, z: S) -> Self {
+        Vec([x, y, z])
+    }
+}
+
+impl<S> Vec<4, S> {
+    #[inline(always)]
+    pub const fn new(x: S, y: S, z: S, w: S) -> Self {
+        Vec([x, y, z, w])
+    }
+}
+
+impl<const V: usize, S: Scalar> Vec<V, S> {
+    pub const ZERO: Self = Vec([S::ZERO; V]);
+
+    #[inline(always)]
+    pub fn map<T>(self, f: impl FnMut(S) -> T) -> Vec<V, T> {
+        Vec(self.0.map(f))
+    }
+
+    #[inline(always)]
+    pub fn dot(self, other: Self) -> S {
+        let mut result = S::ZERO;
+        for (&a, &b) in self.0.iter().zip(other.0.iter()) {
+            result += a * b;
+        }
+        result
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> S {
+        self.dot(self).sqrt()
+    }
+
+    #[inline(always)]
+    pub fn distance(self, other: Self) -> S {
+        (other - self).length()
+    }
+
+    #[inline(always)]
+    pub fn normalize(self) -> Self {
+        let mut length = self.length();
+        if length == S::ZERO {
+            length += S::EPSILON;
+        }
+        self * (S::ONE / length)
+    }
+
+    //  `None` when the vector is too short to have a meaningful direction, or isn't a number.
+    #[inline(always)]
+    pub fn try_normalize(self) -> Option<Self> {
+        let length = self.length();
+        (length > S::EPSILON).then(|| self * (S::ONE / length))
+    }
+
+    #[inline(always)]
+    pub fn normalize_or_zero(self) -> Self {
+        self.try_normalize().unwrap_or(Self::ZERO)
+    }
+}
+
+impl<S: Scalar> Vec<3, S> {
+    #[inline(always)]
+    pub fn cross(self, other: Self) -> Self {
+        let (a, b) = (self, other);
+        Vec::<3, S>::new(
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        )
+    }
+
+    #[inline(always)]
+    pub fn extend(self, w: S) -> Vec4<S> {
+        Vec::<4, S>::new(self[0], self[1], self[2], w)
+    }
+}
+
+impl<S: Scalar> Vec<4, S> {
+    #[inline(always)]
+    pub fn truncate(self) -> Vec3<S> {
+        Vec::<3, S>::new(self[0], self[1], self[2])
+    }
+}
+
+impl<const V: usize, S> Index<usize> for Vec<V, S> {
+    type Output = S;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &S {
+        &self.0[index]
+    }
+}
+
+impl<const V: usize, S> IndexMut<usize> for Vec<V, S> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut S {
+        &mut self.0[index]
+    }
+}
+
+impl<const V: usize, S: Scalar> AddAssign for Vec<V, S> {
+    #[inline(always)]
+    fn add_assign(&mut self, other: Self) {
+        for (a, &b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a += b;
+        }
+    }
+}
+
+impl<const V: usize, S: Scalar> SubAssign for Vec<V, S> {
+    #[inline(always)]
+    fn sub_assign(&mut self, other: Self) {
+        for (a, &b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const V: usize, S: Scalar> MulAssign<S> for Vec<V, S> {
+    #[inline(always)]
+    fn mul_assign(&mut self, s: S) {
+        for a in self.0.iter_mut() {
+            *a *= s;
+        }
+    }
+}
+
+impl<const V: usize, S: Scalar> Add for Vec<V, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const V: usize, S: Scalar> Sub for Vec<V, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const V: usize, S: Scalar> Mul<S> for Vec<V, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(mut self, s: S) -> Self {
+        self *= s;
+        self
+    }
+}
+
+impl<const V: usize, S: Scalar> Div<S> for Vec<V, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, s: S) -> Self {
+        self.map(|a| a / s)
+    }
+}
+
+impl<const V: usize, S: Scalar> Neg for Vec<V, S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<S: Scalar> Mat4<S> {
+    pub const IDENTITY: Self = {
+        let (o, l) = (S::ZERO, S::ONE);
+        Mat4([
+            Vec([l, o, o, o]),
+            Vec([o, l, o, o]),
+            Vec([o, o, l, o]),
+            Vec([o, o, o, l]),
+        ])
+    };
+
+    #[inline(always)]
+    pub fn map<T>(self, mut f: impl FnMut(S) -> T) -> Mat4<T> {
+        Mat4(self.0.map(|row| row.map(&mut f)))
+    }
+
+    pub fn transpose(self) -> Self {
+        let m = self.0;
+        Mat4(core::array::from_fn(|r| {
+            Vec(core::array::from_fn(|c| m[c][r]))
+        }))
+    }
+
+    //  `None` for singular matrices.
+    //  Rotations, scales and translations skip the general case, see `affine_inverse`.
+    pub fn inverse(self) -> Option<Self> {
+        let (o, l) = (S::ZERO, S::ONE);
+        if self[0][3] == o && self[1][3] == o && self[2][3] == o && self[3][3] == l {
+            self.affine_inverse()
+        } else {
+            self.general_inverse()
+        }
+    }
+
+    //  Laplace expansion over 2x2 minors of the top and bottom two rows.
+    fn general_inverse(self) -> Option<Self> {
+        let (o, l) = (S::ZERO, S::ONE);
+        let m = self.0.map(|row| row.0);
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det == o {
+            return None;
+        }
+
+        let inverse = Mat4([
+            Vec([
+                m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3,
+                -m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3,
+                m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3,
+                -m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3,
+            ]),
+            Vec([
+                -m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1,
+                m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1,
+                -m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1,
+                m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1,
+            ]),
+            Vec([
+                m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0,
+                -m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0,
+                m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0,
+                -m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0,
+            ]),
+            Vec([
+                -m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0,
+                m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0,
+                -m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0,
+                m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0,
+            ]),
+        ]);
+        let inv_det = l / det;
+        Some(inverse.map(|s| s * inv_det))
+    }
+
+    //  Only valid when the last column is `[0, 0, 0, 1]`, like every model and view matrix here.
+    //  Inverts the 3x3 part and runs the negated translation through it.
+    pub fn affine_inverse(self) -> Option<Self> {
+        let o = S::ZERO;
+        let [r0, r1, r2] = [0, 1, 2].map(|row| self[row].truncate());
+        let det = r0.dot(r1.cross(r2));
+        if det == o {
+            return None;
+        }
+
+        //  The columns of the inverse are these cross products over the determinant.
+        let columns = [r1.cross(r2), r2.cross(r0), r0.cross(r1)].map(|column| column / det);
+        let mut inverse = Mat4::IDENTITY;
+        for (c, column) in columns.iter().enumerate() {
+            for r in 0..3 {
+                inverse[r][c] = column[r];
+            }
+        }
+        let translation = inverse * self[3].truncate().extend(o);
+        inverse[3] = (-translation.truncate()).extend(S::ONE);
+        Some(inverse)
+    }
+}
+
+impl<S> Index<usize> for Mat4<S> {
+    type Output = Vec4<S>;
+
+    #[inline(always)]
+    fn index(&self, row: usize) -> &Vec4<S> {
+        &self.0[row]
+    }
+}
+
+impl<S> IndexMut<usize> for Mat4<S> {
+    #[inline(always)]
+    fn index_mut(&mut self, row: usize) -> &mut Vec4<S> {
+        &mut self.0[row]
+    }
+}
+
+impl<S: Scalar> Mul<Vec4<S>> for Mat4<S> {
+    type Output = Vec4<S>;
+
+    #[inline(always)]
+    fn mul(self, vec: Vec4<S>) -> Vec4<S> {
+        let mut result = Vec4::ZERO;
+        for (row, &s) in self.0.iter().zip(vec.0.iter()) {
+            result += *row * s;
+        }
+        result
+    }
+}
+
+impl<S: Scalar> Mul for Mat4<S> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, first: Self) -> Self {
+        Mat4(first.0.map(|row| self * row))
+    }
+}
+
+//  Unit quaternion, `[x, y, z]` being the rotation axis scaled by the sine of half the angle.
+//  Composes like `Mat4`: `a * b` applies `b` first, and `to_mat4` matches `mat4_rotate`.
+//  Nothing in the game turns with it yet, only the tests use it.
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat<S = f32>(pub Vec4<S>);
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl<S: Scalar> Quat<S> {
+    pub const IDENTITY: Self = Quat(Vec([S::ZERO, S::ZERO, S::ZERO, S::ONE]));
+
+    pub fn from_axis_angle(axis: Vec3<S>, angle: S) -> Self {
+        let half = angle / (S::ONE + S::ONE);
+        Quat((axis.normalize() * half.sin()).extend(half.cos()))
+    }
+
+    //  Turns `[1, 0, 0]` into the player's forward, `[cos(yaw), 0, sin(yaw)]` at no pitch,
+    //  with positive pitch looking up.
+    pub fn from_yaw_pitch(yaw: S, pitch: S) -> Self {
+        let (o, l) = (S::ZERO, S::ONE);
+        Quat::from_axis_angle(Vec3::new(o, l, o), yaw)
+            * Quat::from_axis_angle(Vec3::new(o, o, l), -pitch)
+    }
+
+    #[inline(always)]
+    pub fn normalize(self) -> Self {
+        Quat(self.0.normalize())
+    }
+
+    //  Constant speed interpolation along the shorter arc, `t` from 0.0 at `self` to 1.0 at `to`.
+    pub fn slerp(self, to: Self, t: S) -> Self {
+        let l = S::ONE;
+        let (mut to, mut cos) = (to.0, self.0.dot(to.0));
+        if cos < S::ZERO {
+            to = -to;
+            cos = -cos;
+        }
+
+        //  Nearly parallel, the sine below gets too small to divide by.
+        if cos > S::from_f32(0.9995) {
+            return Quat(self.0 + (to - self.0) * t).normalize();
+        }
+
+        let angle = cos.acos();
+        let sin = angle.sin();
+        let from_weight = ((l - t) * angle).sin() / sin;
+        let to_weight = (t * angle).sin() / sin;
+        Quat(self.0 * from_weight + to * to_weight)
+    }
+
+    pub fn to_mat4(self) -> Mat4<S> {
+        let (o, l) = (S::ZERO, S::ONE);
+        let two = l + l;
+        let Vec([x, y, z, w]) = self.0;
+        Mat4([
+            Vec([
+                l - two * (y * y + z * z),
+                two * (x * y - z * w),
+                two * (x * z + y * w),
+                o,
+            ]),
+            Vec([
+                two * (x * y + z * w),
+                l - two * (x * x + z * z),
+                two * (y * z - x * w),
+                o,
+            ]),
+            Vec([
+                two * (x * z - y * w),
+                two * (y * z + x * w),
+                l - two * (x * x + y * y),
+                o,
+            ]),
+            Vec([o, o, o, l]),
+        ])
+    }
+}
+
+impl<S: Scalar> Mul for Quat<S> {
+    type Output = Self;
+
+    //  The Hamilton product `first * self`, so the order lines up with `Mat4`.
+    #[inline(always)]
+    fn mul(self, first: Self) -> Self {
+        let Vec([px, py, pz, pw]) = first.0;
+        let Vec([qx, qy, qz, qw]) = self.0;
+        Quat(Vec([
+            pw * qx + px * qw + py * qz - pz * qy,
+            pw * qy - px * qz + py * qw + pz * qx,
+            pw * qz + px * qy - py * qx + pz * qw,
+            pw * qw - px * qx - py * qy - pz * qz,
+        ]))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb<S = f32> {
+    pub min: Vec3<S>,
+    pub max: Vec3<S>,
+}
+
+//  `direction` is kept unit length so hit distances come out in world units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray<S = f32> {
+    pub origin: Vec3<S>,
+    pub direction: Vec3<S>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere<S = f32> {
+    pub center: Vec3<S>,
+    pub radius: S,
+}
+
+//  Where a ray or segment first touches a shape, `normal` being the surface it went through.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit<S = f32> {
+    pub distance: S,
+    pub normal: Vec3<S>,
+}
+
+//  How two shapes overlap, moving the first by `normal * depth` separates them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact<S = f32> {
+    pub depth: S,
+    pub normal: Vec3<S>,
+}
+
+impl<S: Scalar> Aabb<S> {
+    //  Works for negative extents too, walls are described that way.
+    pub fn from_center_extents(center: Vec3<S>, extents: Vec3<S>) -> Self {
+        let (a, b) = (center - extents, center + extents);
+        let mut aabb = Aabb { min: a, max: b };
+        for axis in 0..3 {
+            if b[axis] < a[axis] {
+                aabb.min[axis] = b[axis];
+                aabb.max[axis] = a[axis];
+            }
+        }
+        aabb
+    }
+
+    pub fn closest_point(&self, point: Vec3<S>) -> Vec3<S> {
+        let mut closest = point;
+        for axis in 0..3 {
+            if closest[axis] < self.min[axis] {
+                closest[axis] = self.min[axis];
+            } else if closest[axis] > self.max[axis] {
+                closest[axis] = self.max[axis];
+            }
+        }
+        closest
+    }
+
+    //  A segment starting inside the box hits at distance 0.0, through the face it would have
+    //  entered. `None` for a zero length segment.
+    pub fn intersect_segment(&self, start: Vec3<S>, end: Vec3<S>) -> Option<Hit<S>> {
+        let ray = Ray {
+            origin: start,
+            direction: (end - start).try_normalize()?,
+        };
+        ray.intersect_aabb(self, start.distance(end))
+    }
+}
+
+impl<S: Scalar> Ray<S> {
+    //  Slab test up to `max_distance`, without infinities so it works the same on `Fixed`.
+    fn intersect_aabb(&self, aabb: &Aabb<S>, max_distance: S) -> Option<Hit<S>> {
+        let (o, l) = (S::ZERO, S::ONE);
+        let mut enter: Option<(S, usize, S)> = None;
+        let mut exit = max_distance;
+        for axis in 0..3 {
+            let origin = self.origin[axis];
+            let direction = self.direction[axis];
+            if direction == o {
+                //  Parallel to this slab, it has to start inside it.
+                if origin < aabb.min[axis] || origin > aabb.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv_direction = l / direction;
+            let mut near = (aabb.min[axis] - origin) * inv_direction;
+            let mut far = (aabb.max[axis] - origin) * inv_direction;
+            let mut side = -l;
+            if near > far {
+                core::mem::swap(&mut near, &mut far);
+                side = l;
+            }
+            if enter.is_none_or(|(distance, _, _)| near > distance) {
+                enter = Some((near, axis, side));
+            }
+            if far < exit {
+                exit = far;
+            }
+        }
+
+        let (enter, axis, side) = enter?;
+        if enter > exit || exit < o {
+            return None;
+        }
+        let mut normal = Vec3::<S>::ZERO;
+        normal[axis] = side;
+        Some(Hit {
+            distance: if enter > o { enter } else { o },
+            normal,
+        })
+    }
+}
+
+impl<S: Scalar> Sphere<S> {
+    //  The normal points from the box towards the sphere.
+    pub fn intersect_aabb(&self, aabb: &Aabb<S>) -> Option<Contact<S>> {
+        let (o, l) = (S::ZERO, S::ONE);
+        let closest = aabb.closest_point(self.center);
+        let offset = self.center - closest;
+        let distance = offset.length();
+        if distance > self.radius {
+            return None;
+        }
+        if let Some(normal) = offset.try_normalize() {
+            return Some(Contact {
+                depth: self.radius - distance,
+                normal,
+            });
+        }
+
+        //  The center is inside, push out through the nearest face.
+        let mut contact = Contact {
+            depth: o,
+            normal: Vec3::<S>::ZERO,
+        };
+        let mut nearest: Option<S> = None;
+        for axis in 0..3 {
+            for (gap, side) in [
+                (self.center[axis] - aabb.min[axis], -l),
+                (aabb.max[axis] - self.center[axis], l),
+            ] {
+                if nearest.is_none_or(|nearest| gap < nearest) {
+                    nearest = Some(gap);
+                    contact.depth = gap + self.radius;
+                    contact.normal = Vec3::<S>::ZERO;
+                    contact.normal[axis] = side;
+                }
+            }
+        }
+        Some(contact)
+    }
+
+    //  The normal points from `other` towards `self`.
+    pub fn intersect_sphere(&self, other: &Sphere<S>) -> Option<Contact<S>> {
+        let (o, l) = (S::ZERO, S::ONE);
+        let offset = self.center - other.center;
+        let distance = offset.length();
+        let reach = self.radius + other.radius;
+        if distance > reach {
+            return None;
+        }
+        Some(Contact {
+            depth: reach - distance,
+            //  Right on top of each other, any direction will do.
+            normal: offset.try_normalize().unwrap_or(Vec3::<S>::new(l, o, o)),
+        })
+    }
+}
+
+//  The free function forms stay around while code moves over to the operators.
+
+#[inline(always)]
+pub fn mat4_identity<S: Scalar>() -> Mat4<S> {
+    Mat4::IDENTITY
+}
+
+//  Moves values between scalar types, a no-op when they are the same.
+#[inline(always)]
+pub fn vec_cast<const V: usize, S: Scalar, T: Scalar>(v: Vec<V, S>) -> Vec<V, T> {
+    v.map(|s| T::from_f32(s.to_f32()))
+}
 
 #[inline(always)]
-pub fn mat4_identity() -> Mat4 {
-    [
-        [1.0, 0.0, 0.0, 0.0],
-        [0.0, 1.0, 0.0, 0.0],
-        [0.0, 0.0, 1.0, 0.0],
-        [0.0, 0.0, 0.0, 1.0],
-    ]
+pub fn mat4_cast<S: Scalar, T: Scalar>(mat: Mat4<S>) -> Mat4<T> {
+    mat.map(|s| T::from_f32(s.to_f32()))
 }
 
 #[inline(always)]
-pub fn mat4_translate(mut mat: Mat4, translation: Vec3) -> Mat4 {
+pub fn mat4_translate<S: Scalar>(mut mat: Mat4<S>, translation: Vec3<S>) -> Mat4<S> {
     mat[3][0] += translation[0];
     mat[3][1] += translation[1];
     mat[3][2] += translation[2];
@@ -24,7 +632,7 @@ pub fn mat4_translate(mut mat: Mat4, translation: Vec3) -> Mat4 {
 }
 
 #[inline(always)]
-pub fn mat4_scale(mut mat: Mat4, scale: Vec3) -> Mat4 {
+pub fn mat4_scale<S: Scalar>(mut mat: Mat4<S>, scale: Vec3<S>) -> Mat4<S> {
     mat[0][0] *= scale[0];
     mat[1][1] *= scale[1];
     mat[2][2] *= scale[2];
@@ -32,227 +640,188 @@ pub fn mat4_scale(mut mat: Mat4, scale: Vec3) -> Mat4 {
 }
 
 #[inline(always)]
-pub fn mat4_rotate(mat: Mat4, angle: f32, rot: Vec3) -> Mat4 {
+pub fn mat4_rotate<S: Scalar>(mat: Mat4<S>, angle: S, rot: Vec3<S>) -> Mat4<S> {
+    let (o, l) = (S::ZERO, S::ONE);
     let c = angle.cos();
     let s = angle.sin();
-    let axis = vec_normalize(rot);
-    let rot_mat = [
-        [
-            c + axis[0] * axis[0] * (1.0 - c),
-            axis[0] * axis[1] * (1.0 - c) - axis[2] * s,
-            axis[0] * axis[2] * (1.0 - c) + axis[1] * s,
-            0.0,
-        ],
-        [
-            axis[1] * axis[0] * (1.0 - c) + axis[2] * s,
-            c + axis[1] * axis[1] * (1.0 - c),
-            axis[1] * axis[2] * (1.0 - c) - axis[0] * s,
-            0.0,
-        ],
-        [
-            axis[2] * axis[0] * (1.0 - c) - axis[1] * s,
-            axis[2] * axis[1] * (1.0 - c) + axis[0] * s,
-            c + axis[2] * axis[2] * (1.0 - c),
-            0.0,
-        ],
-        [0.0, 0.0, 0.0, 1.0],
-    ] as Mat4;
+    let axis = rot.normalize();
+    let rot_mat = Mat4([
+        Vec([
+            c + axis[0] * axis[0] * (l - c),
+            axis[0] * axis[1] * (l - c) - axis[2] * s,
+            axis[0] * axis[2] * (l - c) + axis[1] * s,
+            o,
+        ]),
+        Vec([
+            axis[1] * axis[0] * (l - c) + axis[2] * s,
+            c + axis[1] * axis[1] * (l - c),
+            axis[1] * axis[2] * (l - c) - axis[0] * s,
+            o,
+        ]),
+        Vec([
+            axis[2] * axis[0] * (l - c) - axis[1] * s,
+            axis[2] * axis[1] * (l - c) + axis[0] * s,
+            c + axis[2] * axis[2] * (l - c),
+            o,
+        ]),
+        Vec([o, o, o, l]),
+    ]);
 
-    mat4_mul_mat4(mat, rot_mat)
+    rot_mat * mat
 }
 
 #[inline(always)]
-pub fn mat4_get_projection(aspect: f32, fov_rad: f32, near: f32, far: f32) -> Mat4 {
-    [
-        [aspect / (fov_rad / 2.0).tan(), 0.0, 0.0, 0.0],
-        [0.0, 1.0 / (fov_rad / 2.0).tan(), 0.0, 0.0],
-        [0.0, 0.0, far / (far - near), 1.0],
-        [0.0, 0.0, (-far * near) / (far - near), 0.0],
-    ]
+pub fn mat4_get_projection<S: Scalar>(aspect: S, fov_rad: S, near: S, far: S) -> Mat4<S> {
+    let (o, l) = (S::ZERO, S::ONE);
+    let half_fov = fov_rad / (l + l);
+    Mat4([
+        Vec([aspect / half_fov.tan(), o, o, o]),
+        Vec([o, l / half_fov.tan(), o, o]),
+        Vec([o, o, far / (far - near), l]),
+        Vec([o, o, (-far * near) / (far - near), o]),
+    ])
 }
 
 #[inline(always)]
-pub fn mat4_get_look_at(position: Vec3, center: Vec3, up: Vec3) -> Mat4 {
-    let dir = vec_sub_vec(center, position);
-    let right = vec_normalize(vec3_cross_product(up, dir));
-    let mat_up = vec3_cross_product(dir, right);
-
-    [
-        [right[0], mat_up[0], dir[0], 0.0],
-        [right[1], mat_up[1], dir[1], 0.0],
-        [right[2], mat_up[2], dir[2], 0.0],
-        [
-            -vec_dot(position, right),
-            -vec_dot(position, mat_up),
-            -vec_dot(position, dir),
-            1.0,
-        ],
-    ]
+pub fn mat4_get_look_at<S: Scalar>(position: Vec3<S>, center: Vec3<S>, up: Vec3<S>) -> Mat4<S> {
+    let (o, l) = (S::ZERO, S::ONE);
+    let dir = center - position;
+    let right = up.cross(dir).normalize();
+    let mat_up = dir.cross(right);
+
+    Mat4([
+        Vec([right[0], mat_up[0], dir[0], o]),
+        Vec([right[1], mat_up[1], dir[1], o]),
+        Vec([right[2], mat_up[2], dir[2], o]),
+        Vec([
+            -position.dot(right),
+            -position.dot(mat_up),
+            -position.dot(dir),
+            l,
+        ]),
+    ])
 }
 
+//  `b` is applied first, same as `a * b`.
 #[inline(always)]
-pub fn mat4_mul_mat4(b: Mat4, a: Mat4) -> Mat4 {
-    let mut result = mat4_identity();
-    for r in 0..4 {
-        for c in 0..4 {
-            result[r][c] =
-                b[r][0] * a[0][c] + b[r][1] * a[1][c] + b[r][2] * a[2][c] + b[r][3] * a[3][c];
-        }
-    }
-    result
+pub fn mat4_mul_mat4<S: Scalar>(b: Mat4<S>, a: Mat4<S>) -> Mat4<S> {
+    a * b
 }
 
 #[inline(always)]
-pub fn mat4_mul_vec4(mat: Mat4, vec: Vec4) -> Vec4 {
-    let mut result = [0.0, 0.0, 0.0, 0.0] as Vec4;
-
-    result[0] = vec[0] * mat[0][0] + vec[1] * mat[1][0] + vec[2] * mat[2][0] + vec[3] * mat[3][0];
-    result[1] = vec[0] * mat[0][1] + vec[1] * mat[1][1] + vec[2] * mat[2][1] + vec[3] * mat[3][1];
-    result[2] = vec[0] * mat[0][2] + vec[1] * mat[1][2] + vec[2] * mat[2][2] + vec[3] * mat[3][2];
-    result[3] = vec[0] * mat[0][3] + vec[1] * mat[1][3] + vec[2] * mat[2][3] + vec[3] * mat[3][3];
-
-    result
+pub fn mat4_transpose<S: Scalar>(mat: Mat4<S>) -> Mat4<S> {
+    mat.transpose()
 }
 
 #[inline(always)]
-pub fn vec4_scale_with_w(mut vec: Vec4) -> Vec4 {
-    if vec[3] != 0.0 {
-        vec[0] /= vec[3];
-        vec[1] /= vec[3];
-        vec[2] /= vec[3];
-    }
-    vec
+pub fn mat4_inverse<S: Scalar>(mat: Mat4<S>) -> Option<Mat4<S>> {
+    mat.inverse()
 }
 
 #[inline(always)]
-pub fn vec3_into_vec4(vec: Vec3) -> Vec4 {
-    [vec[0], vec[1], vec[2], 1f32]
+pub fn mat4_mul_vec4<S: Scalar>(mat: Mat4<S>, vec: Vec4<S>) -> Vec4<S> {
+    mat * vec
 }
 
 #[inline(always)]
-pub fn vec4_into_vec3(vec: Vec4) -> Vec3 {
-    [vec[0], vec[1], vec[2]]
+pub fn vec4_scale_with_w<S: Scalar>(vec: Vec4<S>) -> Vec4<S> {
+    if vec[3] != S::ZERO {
+        (vec.truncate() / vec[3]).extend(vec[3])
+    } else {
+        vec
+    }
 }
 
 #[inline(always)]
-pub fn vec3_cross_product(a: Vec3, b: Vec3) -> Vec3 {
-    let mut result = [0.0, 0.0, 0.0] as Vec3;
-    result[0] = a[1] * b[2] - a[2] * b[1];
-    result[1] = a[2] * b[0] - a[0] * b[2];
-    result[2] = a[0] * b[1] - a[1] * b[0];
-    result
+pub fn vec3_into_vec4<S: Scalar>(vec: Vec3<S>) -> Vec4<S> {
+    vec.extend(S::ONE)
 }
 
 #[inline(always)]
-pub fn vec_add_vec<const V: usize>(mut a: Vec<V>, b: Vec<V>) -> Vec<V> {
-    for (a, b) in a.iter_mut().zip(b.iter()) {
-        *a += b;
-    }
-    a
+pub fn vec4_into_vec3<S: Scalar>(vec: Vec4<S>) -> Vec3<S> {
+    vec.truncate()
 }
 
 #[inline(always)]
-pub fn vec_sub_vec<const V: usize>(mut a: Vec<V>, b: Vec<V>) -> Vec<V> {
-    for (a, b) in a.iter_mut().zip(b.iter()) {
-        *a -= b;
-    }
-    a
+pub fn vec3_cross_product<S: Scalar>(a: Vec3<S>, b: Vec3<S>) -> Vec3<S> {
+    a.cross(b)
 }
 
 #[inline(always)]
-pub fn vec_distance<const V: usize>(a: Vec<V>, b: Vec<V>) -> f32 {
-    vec_length(vec_sub_vec(b, a))
+pub fn vec_add_vec<const V: usize, S: Scalar>(a: Vec<V, S>, b: Vec<V, S>) -> Vec<V, S> {
+    a + b
 }
 
 #[inline(always)]
-pub fn vec_add_scalar<const V: usize>(mut v: [f32; V], s: f32) -> [f32; V] {
-    for vs in v.iter_mut() {
-        *vs += s;
-    }
-    v
+pub fn vec_sub_vec<const V: usize, S: Scalar>(a: Vec<V, S>, b: Vec<V, S>) -> Vec<V, S> {
+    a - b
 }
 
 #[inline(always)]
-pub fn vec_mul_scalar<const V: usize>(mut v: [f32; V], s: f32) -> [f32; V] {
-    for vs in v.iter_mut() {
-        *vs *= s;
-    }
-    v
+pub fn vec_add_scalar<const V: usize, S: Scalar>(v: Vec<V, S>, s: S) -> Vec<V, S> {
+    v.map(|a| a + s)
 }
 
 #[inline(always)]
-pub fn vec_normalize<const V: usize>(v: [f32; V]) -> [f32; V] {
-    let mut length = vec_length(v);
-    if length == 0.0 {
-        length += 0.00001;
-    }
-    vec_mul_scalar(v, 1.0 / length)
+pub fn vec_normalize<const V: usize, S: Scalar>(v: Vec<V, S>) -> Vec<V, S> {
+    v.normalize()
 }
 
 #[inline(always)]
-pub fn vec_dot<const V: usize>(a: Vec<V>, b: Vec<V>) -> f32 {
-    let mut result = 0.0;
-    for (a, b) in a.iter().zip(b.iter()) {
-        result += a * b;
-    }
-    result
+pub fn vec_dot<const V: usize, S: Scalar>(a: Vec<V, S>, b: Vec<V, S>) -> S {
+    a.dot(b)
 }
 
 #[inline(always)]
-pub fn vec_length<const V: usize>(v: [f32; V]) -> f32 {
-    let mut sqsum = 0.0;
-    for vs in v.iter() {
-        sqsum += vs * vs;
-    }
-    sqsum.sqrt()
+pub fn vec_length<const V: usize, S: Scalar>(v: Vec<V, S>) -> S {
+    v.length()
 }
 
 #[inline(always)]
-pub fn vec_intersects_plane<const V: usize>(
-    plane_point: Vec<V>,
-    plane_normal: Vec<V>,
-    line_start: Vec<V>,
-    line_end: Vec<V>,
-) -> Vec<V> {
-    let d = -vec_dot(plane_normal, plane_point);
-    let ad = vec_dot(line_start, plane_normal);
-    let bd = vec_dot(line_end, plane_normal);
+pub fn vec_intersects_plane<const V: usize, S: Scalar>(
+    plane_point: Vec<V, S>,
+    plane_normal: Vec<V, S>,
+    line_start: Vec<V, S>,
+    line_end: Vec<V, S>,
+) -> Vec<V, S> {
+    let d = -plane_normal.dot(plane_point);
+    let ad = line_start.dot(plane_normal);
+    let bd = line_end.dot(plane_normal);
     let mut td = bd - ad;
-    if td == 0.0 {
-        td += 0.00001;
+    if td == S::ZERO {
+        td += S::EPSILON;
     }
     let t = (-d - ad) / td;
-    let line_start_to_end = vec_sub_vec(line_end, line_start);
-    let line_to_intersect = vec_mul_scalar(line_start_to_end, t);
-    vec_add_vec(line_start, line_to_intersect)
+    line_start + (line_end - line_start) * t
 }
 
-pub fn triangle_clip_plane(
-    plane_point: Vec3,
-    plane_normal: Vec3,
-    triangle: (Vec3, Vec3, Vec3),
-) -> SmallVec<[(Vec3, Vec3, Vec3); 2]> {
-    let dist =
-        |point: Vec3| -> f32 { vec_dot(plane_normal, point) - vec_dot(plane_normal, plane_point) };
+pub fn triangle_clip_plane<const V: usize, S: Scalar>(
+    plane_point: Vec<V, S>,
+    plane_normal: Vec<V, S>,
+    triangle: Triangle<V, S>,
+) -> SmallVec<[Triangle<V, S>; 2]> {
+    let dist = |point: Vec<V, S>| -> S {
+        vec_dot(plane_normal, point) - vec_dot(plane_normal, plane_point)
+    };
 
-    let mut inside_points: SmallVec<[Vec3; 3]> = smallvec![];
-    let mut outside_points: SmallVec<[Vec3; 3]> = smallvec![];
+    let mut inside_points: SmallVec<[Vec<V, S>; 3]> = smallvec![];
+    let mut outside_points: SmallVec<[Vec<V, S>; 3]> = smallvec![];
 
     let d0 = dist(triangle.0);
     let d1 = dist(triangle.1);
     let d2 = dist(triangle.2);
 
-    if d0 >= 0.0 {
+    if d0 >= S::ZERO {
         inside_points.push(triangle.0);
     } else {
         outside_points.push(triangle.0)
     }
-    if d1 >= 0.0 {
+    if d1 >= S::ZERO {
         inside_points.push(triangle.1);
     } else {
         outside_points.push(triangle.1);
     }
-    if d2 >= 0.0 {
+    if d2 >= S::ZERO {
         inside_points.push(triangle.2);
     } else {
         outside_points.push(triangle.2);
@@ -304,3 +873,351 @@ pub fn triangle_clip_plane(
 
     smallvec![]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Fixed;
+    use nanorand::{Rng, WyRand};
+
+    const CASES: usize = 1000;
+
+    fn random(rng: &mut WyRand, range: f32) -> f32 {
+        (rng.generate::<f32>() * 2.0 - 1.0) * range
+    }
+
+    fn random_vec3(rng: &mut WyRand, range: f32) -> Vec3 {
+        Vec3::new(random(rng, range), random(rng, range), random(rng, range))
+    }
+
+    //  Rotated, then moved up to `reach` along each axis, no scale.
+    fn random_rigid(rng: &mut WyRand, reach: f32) -> Mat4 {
+        let axis = random_vec3(rng, 1.0) + Vec3::new(0.0, 0.0, 0.01);
+        mat4_translate(
+            mat4_rotate(mat4_identity(), random(rng, 3.0), axis),
+            random_vec3(rng, reach),
+        )
+    }
+
+    fn largest(m: Mat4) -> f32 {
+        m.0.iter()
+            .flat_map(|row| row.0)
+            .map(f32::abs)
+            .fold(1.0, f32::max)
+    }
+
+    fn assert_mat4_near(a: Mat4, b: Mat4, tolerance: f32) {
+        for row in 0..4 {
+            for column in 0..4 {
+                assert!(
+                    (a[row][column] - b[row][column]).abs() <= tolerance,
+                    "{:?}\n{:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_the_matrix() {
+        let mut rng = WyRand::new_seed(16);
+        for _ in 0..CASES {
+            //  A model, a whole view projection and one with every entry random, the last two
+            //  going through the general case.
+            let model = mat4_mul_mat4(
+                mat4_scale(
+                    mat4_identity(),
+                    random_vec3(&mut rng, 4.0) + Vec3::new(5.0, 5.0, 5.0),
+                ),
+                random_rigid(&mut rng, 100.0),
+            );
+            let view_projection = mat4_mul_mat4(
+                random_rigid(&mut rng, 10.0),
+                mat4_get_projection(0.8, 1.2, 1.0, random(&mut rng, 50.0) + 60.0),
+            );
+            let random = Mat4(core::array::from_fn(|_| {
+                random_vec3(&mut rng, 1.0).extend(random(&mut rng, 1.0))
+            }));
+
+            for (kind, m) in [model, view_projection, random].into_iter().enumerate() {
+                let inverse = mat4_inverse(m).unwrap();
+                //  Nearly singular random ones lose too much to `f32` to say anything.
+                if kind == 2 && largest(inverse) > 100.0 {
+                    continue;
+                }
+                assert_mat4_near(m * inverse, Mat4::IDENTITY, 0.0001);
+                assert_mat4_near(inverse * m, Mat4::IDENTITY, 0.0001);
+            }
+        }
+        assert_eq!(
+            mat4_inverse(mat4_scale(Mat4::IDENTITY, Vec3::<f32>::ZERO)),
+            None
+        );
+    }
+
+    #[test]
+    fn affine_inverse_matches_inverse_on_rigid_transforms() {
+        let mut rng = WyRand::new_seed(17);
+        for _ in 0..CASES {
+            let m = random_rigid(&mut rng, 100.0);
+            assert_mat4_near(
+                m.affine_inverse().unwrap(),
+                m.general_inverse().unwrap(),
+                0.0001,
+            );
+            //  Rigid transforms are orthonormal, so the rotation part inverts by transposing.
+            let inverse = mat4_inverse(m).unwrap();
+            let transposed = mat4_transpose(m);
+            for row in 0..3 {
+                for column in 0..3 {
+                    assert!((inverse[row][column] - transposed[row][column]).abs() <= 0.0001);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quat_to_mat4_matches_mat4_rotate() {
+        let mut rng = WyRand::new_seed(18);
+        for _ in 0..CASES {
+            let axis = random_vec3(&mut rng, 1.0) + Vec3::new(0.0, 0.01, 0.0);
+            let angle = random(&mut rng, 6.0);
+            assert_mat4_near(
+                Quat::from_axis_angle(axis, angle).to_mat4(),
+                mat4_rotate(mat4_identity(), angle, axis),
+                0.0001,
+            );
+        }
+
+        assert_eq!(Quat::<f32>::IDENTITY.to_mat4(), Mat4::IDENTITY);
+        for yaw in [0.0, 0.5, 2.0, -3.0] {
+            let forward = Quat::from_yaw_pitch(yaw, 0.0).to_mat4() * Vec4::new(1.0, 0.0, 0.0, 0.0);
+            let expected = Vec4::new(yaw.cos(), 0.0, yaw.sin(), 0.0);
+            assert!((forward - expected).length() <= 0.0001, "{:?}", forward);
+        }
+        let up = Quat::from_yaw_pitch(0.0, 0.5).to_mat4() * Vec4::new(1.0, 0.0, 0.0, 0.0);
+        assert!(up[1] > 0.0, "{:?}", up);
+    }
+
+    #[test]
+    fn slerp_hits_both_ends_and_the_middle() {
+        let mut rng = WyRand::new_seed(19);
+        for _ in 0..CASES {
+            let from = Quat::from_axis_angle(random_vec3(&mut rng, 1.0), random(&mut rng, 3.0));
+            let to = Quat::from_axis_angle(random_vec3(&mut rng, 1.0), random(&mut rng, 3.0));
+            //  `q` and `-q` are the same rotation, so compare matrices.
+            assert_mat4_near(from.slerp(to, 0.0).to_mat4(), from.to_mat4(), 0.0001);
+            assert_mat4_near(from.slerp(to, 1.0).to_mat4(), to.to_mat4(), 0.0001);
+
+            //  Halfway is as far from either end.
+            let middle = from.slerp(to, 0.5);
+            let from_middle = from.0.dot(middle.0).abs();
+            let middle_to = middle.0.dot(to.0).abs();
+            assert!((from_middle - middle_to).abs() <= 0.0001);
+            assert!((middle.0.length() - 1.0).abs() <= 0.0001);
+        }
+    }
+
+    //  Inputs both sides can hold exactly, as `Fixed` and as `f32`.
+    fn fixed_vec3(v: Vec3) -> (Vec3<Fixed>, Vec3) {
+        let fixed = vec_cast(v);
+        (fixed, vec_cast(fixed))
+    }
+
+    fn fixed_mat4(m: Mat4) -> (Mat4<Fixed>, Mat4) {
+        let fixed = mat4_cast(m);
+        (fixed, mat4_cast(fixed))
+    }
+
+    //  Within `tolerance`, scaled up with values past 1.0 as `Fixed` keeps the same step size
+    //  however big they get.
+    fn assert_fixed_near<const V: usize>(fixed: Vec<V, Fixed>, float: Vec<V>, tolerance: f32) {
+        let fixed: Vec<V> = vec_cast(fixed);
+        for i in 0..V {
+            assert!(
+                (fixed[i] - float[i]).abs() <= tolerance * float[i].abs().max(1.0),
+                "{:?}\n{:?}",
+                fixed,
+                float
+            );
+        }
+    }
+
+    fn assert_fixed_mat4_near(fixed: Mat4<Fixed>, float: Mat4, tolerance: f32) {
+        for row in 0..4 {
+            assert_fixed_near(fixed[row], float[row], tolerance);
+        }
+    }
+
+    #[test]
+    fn fixed_products_agree_with_f32() {
+        let mut rng = WyRand::new_seed(20);
+        for _ in 0..CASES {
+            let mut random_mat4 = || {
+                Mat4(core::array::from_fn(|_| {
+                    random_vec3(&mut rng, 4.0).extend(random(&mut rng, 4.0))
+                }))
+            };
+            let (a, float_a) = fixed_mat4(random_mat4());
+            let (b, float_b) = fixed_mat4(random_mat4());
+            let (v, float_v) = fixed_vec3(random_vec3(&mut rng, 10.0));
+            let (v, float_v) = (v.extend(Fixed::ONE), float_v.extend(1.0));
+
+            assert_fixed_near(mat4_mul_vec4(a, v), mat4_mul_vec4(float_a, float_v), 0.0001);
+            assert_fixed_mat4_near(mat4_mul_mat4(a, b), mat4_mul_mat4(float_a, float_b), 0.0001);
+        }
+    }
+
+    //  `1 / length` is only good to a step of `Fixed`, which gets coarser against it the longer
+    //  the vector, so lengths stop at 50.
+    #[test]
+    fn fixed_normalize_agrees_with_f32() {
+        let mut rng = WyRand::new_seed(21);
+        for _ in 0..CASES {
+            let direction = random_vec3(&mut rng, 1.0) + Vec3::new(0.0, 0.0, 0.01);
+            let length = 0.1 + rng.generate::<f32>() * 49.9;
+            let (v, float_v) = fixed_vec3(direction.normalize() * length);
+            assert_fixed_near(v.normalize(), float_v.normalize(), 0.001);
+        }
+    }
+
+    #[test]
+    fn fixed_projection_and_look_at_agree_with_f32() {
+        let mut rng = WyRand::new_seed(22);
+        for _ in 0..CASES {
+            let fov = 0.8 + rng.generate::<f32>() * 1.2;
+            let near = 0.1 + rng.generate::<f32>() * 0.9;
+            let far = 30.0 + rng.generate::<f32>() * 70.0;
+            let [aspect, fov, near, far] =
+                [0.8, fov, near, far].map(|v| Fixed::from_f32(v).to_f32());
+            assert_fixed_mat4_near(
+                mat4_get_projection(
+                    Fixed::from_f32(aspect),
+                    Fixed::from_f32(fov),
+                    Fixed::from_f32(near),
+                    Fixed::from_f32(far),
+                ),
+                mat4_get_projection(aspect, fov, near, far),
+                0.001,
+            );
+
+            //  The game's camera looks along the floor. Tipped towards `up`, their cross product
+            //  gets short, and `Fixed` loses its direction.
+            let (position, float_position) = fixed_vec3(random_vec3(&mut rng, 30.0));
+            let yaw = random(&mut rng, 3.0);
+            let front = Vec3::new(yaw.cos(), random(&mut rng, 0.5), yaw.sin());
+            let (front, float_front) = fixed_vec3(front.normalize() * 2.0);
+            let (up, float_up) = fixed_vec3(Vec3::new(0.0, 1.0, 0.0));
+            assert_fixed_mat4_near(
+                mat4_get_look_at(position, position + front, up),
+                mat4_get_look_at(float_position, float_position + float_front, float_up),
+                0.001,
+            );
+        }
+    }
+
+    const UNIT_BOX: Aabb = Aabb {
+        min: Vec3::new(-1.0, -1.0, -1.0),
+        max: Vec3::new(1.0, 1.0, 1.0),
+    };
+
+    fn assert_hit_near(hit: Option<Hit>, distance: f32, normal: Vec3) {
+        let hit = hit.unwrap();
+        assert!((hit.distance - distance).abs() <= 0.0001, "{:?}", hit);
+        assert_eq!(hit.normal, normal);
+    }
+
+    #[test]
+    fn segments_hit_boxes_through_the_face_they_enter() {
+        //  Straight in through -X, and slanted in through +Z after crossing the X slab.
+        assert_hit_near(
+            UNIT_BOX.intersect_segment(Vec3::new(-5.0, 0.5, 0.0), Vec3::new(5.0, 0.5, 0.0)),
+            4.0,
+            Vec3::new(-1.0, 0.0, 0.0),
+        );
+        assert_hit_near(
+            UNIT_BOX.intersect_segment(Vec3::new(-1.5, 0.0, 3.0), Vec3::new(1.5, 0.0, -1.0)),
+            2.5,
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+
+        //  Parallel to the Y slab, above it, and along its face.
+        let above = Vec3::new(0.0, 1.5, 0.0);
+        let face = Vec3::new(0.0, 1.0, 0.0);
+        let along = Vec3::new(10.0, 0.0, 0.0);
+        assert_eq!(
+            UNIT_BOX.intersect_segment(above - along, above + along),
+            None
+        );
+        assert_hit_near(
+            UNIT_BOX.intersect_segment(face - along, face + along),
+            9.0,
+            Vec3::new(-1.0, 0.0, 0.0),
+        );
+
+        //  Starting inside, it hits straight away through the face behind it.
+        assert_hit_near(
+            UNIT_BOX.intersect_segment(Vec3::new(0.5, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)),
+            0.0,
+            Vec3::new(-1.0, 0.0, 0.0),
+        );
+
+        //  Ending short of the box, or starting past it.
+        let (start, end) = (Vec3::new(-5.0, 0.0, 0.0), Vec3::new(-2.0, 0.0, 0.0));
+        assert_eq!(UNIT_BOX.intersect_segment(start, end), None);
+        assert_eq!(UNIT_BOX.intersect_segment(-end, -start), None);
+        assert_eq!(UNIT_BOX.intersect_segment(start, start), None);
+    }
+
+    #[test]
+    fn spheres_are_pushed_out_of_boxes() {
+        let contact = |center: Vec3, radius: f32| {
+            Sphere { center, radius }
+                .intersect_aabb(&UNIT_BOX)
+                .map(|contact| (contact.depth, contact.normal))
+        };
+        let assert_contact = |found: Option<(f32, Vec3)>, depth: f32, normal: Vec3| {
+            let (found_depth, found_normal) = found.unwrap();
+            assert!((found_depth - depth).abs() <= 0.0001, "{}", found_depth);
+            assert_eq!(found_normal, normal);
+        };
+
+        assert_eq!(contact(Vec3::new(1.5, 0.0, 0.0), 0.4), None);
+        assert_contact(
+            contact(Vec3::new(1.5, 0.0, 0.0), 0.75),
+            0.25,
+            Vec3::new(1.0, 0.0, 0.0),
+        );
+        //  Off a corner, the normal points away from it.
+        let corner = contact(Vec3::new(2.0, 2.0, 0.0), 1.5).unwrap();
+        assert!((corner.1 - Vec3::new(1.0, 1.0, 0.0).normalize()).length() <= 0.0001);
+
+        //  With the center inside, out through the nearest face and then the radius on top.
+        assert_contact(
+            contact(Vec3::new(0.1, -0.8, 0.3), 0.5),
+            0.7,
+            Vec3::new(0.0, -1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn spheres_are_pushed_apart() {
+        let sphere = |x: f32| Sphere {
+            center: Vec3::new(x, 0.0, 0.0),
+            radius: 1.0,
+        };
+        assert_eq!(sphere(0.0).intersect_sphere(&sphere(2.5)), None);
+
+        let contact = sphere(0.0).intersect_sphere(&sphere(1.5)).unwrap();
+        assert!((contact.depth - 0.5).abs() <= 0.0001);
+        assert_eq!(contact.normal, Vec3::new(-1.0, 0.0, 0.0));
+        let contact = sphere(1.5).intersect_sphere(&sphere(0.0)).unwrap();
+        assert_eq!(contact.normal, Vec3::new(1.0, 0.0, 0.0));
+
+        //  Right on top of each other, they still come apart along something.
+        let contact = sphere(0.0).intersect_sphere(&sphere(0.0)).unwrap();
+        assert_eq!(contact.depth, 2.0);
+        assert_eq!(contact.normal, Vec3::new(1.0, 0.0, 0.0));
+    }
+}
@@ -1,270 +1,19 @@
-pub fn cube() -> &'static [f32] {
-    &[
-        -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0, -1.0, -1.0, -1.0, -1.0, -1.0,
-        1.0, -1.0, 1.0, -1.0, 1.0, -1.0, -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0,
-        -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0,
-        -1.0, -1.0, 1.0, -1.0, -1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
-        1.0, 1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0, -1.0, 1.0,
-        1.0, 1.0, 1.0, 1.0, 1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, 1.0, -1.0, 1.0, -1.0, -1.0, 1.0,
-        1.0, 1.0, 1.0, 1.0, -1.0, 1.0, 1.0, 1.0, -1.0, 1.0,
-    ]
-}
+use super::*;
 
-pub fn person() -> &'static [f32] {
-    &[
-        0.201267, 0.101878, -0.041803, 0.128584, 0.001012, -0.038395, 0.145873, 0.101878,
-        -0.041803, 0.130214, 0.101878, 0.060545, 0.21673, 0.03405, 0.235835, 0.216925, 0.101878,
-        0.060545, 0.115763, -0.001025, 0.242068, 0.21673, 0.03405, 0.235835, 0.137877, 0.042153,
-        0.249172, 0.21673, 0.03405, 0.235835, 0.230771, 0.001012, 0.08831, 0.216925, 0.101878,
-        0.060545, 0.132578, 0.001012, 0.114505, 0.137877, 0.042153, 0.249172, 0.130214, 0.101878,
-        0.060545, 0.128584, 0.001012, -0.038395, 0.130214, 0.101878, 0.060545, 0.145873, 0.101878,
-        -0.041803, 0.216925, 0.101878, 0.060545, 0.218556, 0.001012, -0.056775, 0.201267, 0.101878,
-        -0.041803, 0.132578, 0.001012, 0.114505, 0.231377, -0.001025, 0.224849, 0.115763,
-        -0.001025, 0.242068, 0.230771, 0.001012, 0.08831, 0.128584, 0.001012, -0.038395, 0.218556,
-        0.001012, -0.056775, 0.086958, 0.549551, 0.115117, 0.182317, 0.601477, 0.102363, 0.095652,
-        0.601477, 0.102363, 0.130214, 0.101878, 0.060545, 0.191011, 0.549551, 0.115117, 0.086958,
-        0.549551, 0.115117, 0.145873, 0.101878, -0.041803, 0.217823, 0.446094, -0.058787, 0.201267,
-        0.101878, -0.041803, 0.204018, 0.58598, -0.012714, 0.075994, 0.446094, -0.058787, 0.073952,
-        0.58598, -0.012714, 0.216925, 0.101878, 0.060545, 0.217823, 0.446094, -0.058787, 0.191011,
-        0.549551, 0.115117, 0.130214, 0.101878, 0.060545, 0.075994, 0.446094, -0.058787, 0.145873,
-        0.101878, -0.041803, 0.204018, 0.58598, -0.012714, 0.182317, 0.601477, 0.102363, 0.191011,
-        0.549551, 0.115117, 0.204018, 0.58598, -0.012714, 0.191011, 0.549551, 0.115117, 0.217823,
-        0.446094, -0.058787, 0.095652, 0.601477, 0.102363, 0.073952, 0.58598, -0.012714, 0.086958,
-        0.549551, 0.115117, 0.073952, 0.58598, -0.012714, 0.075994, 0.446094, -0.058787, 0.086958,
-        0.549551, 0.115117, -2e-6, 0.933496, 0.136309, 0.163228, 1.074129, 0.13785, -2e-6,
-        1.042395, 0.146343, 0.192827, 0.939277, -0.069293, 0.163061, 1.061983, -0.071234, 0.163228,
-        1.074129, 0.13785, 0.163061, 1.061983, -0.071234, -2e-6, 0.931916, -0.092766, -2e-6,
-        1.035529, -0.129773, 0.163228, 1.074129, 0.13785, 0.156011, 1.232386, -0.039588, 0.134071,
-        1.232386, 0.113104, 0.163061, 1.061983, -0.071234, -2e-6, 1.228824, -0.08242, 0.156011,
-        1.232386, -0.039588, 0.163228, 1.074129, 0.13785, -2e-6, 1.228824, 0.147699, -2e-6,
-        1.042395, 0.146343, 0.150715, 1.456774, 0.110231, 0.224522, 1.238198, -0.064175, 0.216796,
-        1.213177, 0.033581, 0.312551, 1.261717, -0.078929, 0.224522, 1.238198, -0.064175, 0.29292,
-        1.288112, -0.068648, 0.29292, 1.288112, -0.068648, 0.294348, 1.26189, 0.025875, 0.312551,
-        1.261717, -0.078929, 0.224522, 1.238198, -0.064175, 0.243687, 1.181918, -0.078929,
-        0.216796, 1.213177, 0.033581, 0.216796, 1.213177, 0.033581, 0.348743, 1.005957, 0.056556,
-        0.294348, 1.26189, 0.025875, 0.351491, 1.004101, -0.013116, 0.243687, 1.181918, -0.078929,
-        0.312551, 1.261717, -0.078929, 0.294348, 1.26189, 0.025875, 0.351491, 1.004101, -0.013116,
-        0.312551, 1.261717, -0.078929, 0.243687, 1.181918, -0.078929, 0.312077, 0.975234, 0.056556,
-        0.216796, 1.213177, 0.033581, 0.063203, 1.664156, 0.010681, 0.129298, 1.621634, 0.090801,
-        0.172348, 1.627023, -0.07553, 0.129298, 1.621634, 0.090801, 0.063203, 1.664156, 0.010681,
-        -2e-6, 1.617999, 0.095863, 0.172348, 1.627023, -0.07553, -2e-6, 1.621071, -0.090806,
-        0.063203, 1.664156, 0.010681, 0.16699, 1.504943, -0.104881, -2e-6, 1.621071, -0.090806,
-        0.172348, 1.627023, -0.07553, 0.129298, 1.621634, 0.090801, -2e-6, 1.455858, 0.15026,
-        0.150715, 1.456774, 0.110231, 0.237228, 1.542835, 0.067978, 0.129298, 1.621634, 0.090801,
-        0.150715, 1.456774, 0.110231, 0.237228, 1.542835, 0.067978, 0.29292, 1.288112, -0.068648,
-        0.256866, 1.547362, -0.064066, 0.172348, 1.627023, -0.07553, 0.256866, 1.547362, -0.064066,
-        0.16699, 1.504943, -0.104881, 0.150715, 1.456774, 0.110231, 0.294348, 1.26189, 0.025875,
-        0.237228, 1.542835, 0.067978, 0.16699, 1.504943, -0.104881, 0.29292, 1.288112, -0.068648,
-        0.224522, 1.238198, -0.064175, 0.312077, 0.975234, 0.056556, 0.403226, 0.89242, 0.118543,
-        0.348743, 1.005957, 0.056556, 0.348743, 1.005957, 0.056556, 0.385931, 0.862996, -0.041366,
-        0.351491, 1.004101, -0.013116, 0.312077, 0.975234, 0.056556, 0.309371, 0.943399, -0.026862,
-        0.287163, 0.94535, 0.087145, 0.385931, 0.862996, -0.041366, 0.323792, 0.830571, 0.118543,
-        0.320725, 0.856594, -0.039569, 0.403226, 0.89242, 0.118543, 0.287163, 0.94535, 0.087145,
-        0.323792, 0.830571, 0.118543, 0.287163, 0.94535, 0.087145, 0.320725, 0.856594, -0.039569,
-        0.323792, 0.830571, 0.118543, 0.385931, 0.862996, -0.041366, 0.309371, 0.943399, -0.026862,
-        0.351491, 1.004101, -0.013116, 0.192827, 0.939277, -0.069293, 0.182317, 0.601477, 0.102363,
-        0.204018, 0.58598, -0.012714, 0.073952, 0.58598, -0.012714, 0.192827, 0.939277, -0.069293,
-        0.204018, 0.58598, -0.012714, 0.182317, 0.601477, 0.102363, -2e-6, 0.933496, 0.136309,
-        0.095652, 0.601477, 0.102363, -2e-6, 0.933496, 0.136309, 0.073952, 0.58598, -0.012714,
-        0.095652, 0.601477, 0.102363, 0.063203, 1.664156, 0.010681, -2e-6, 1.667634, 0.090502,
-        -2e-6, 1.617999, 0.095863, 0.063203, 1.664156, 0.010681, -2e-6, 1.694542, -0.06919,
-        0.069369, 1.712631, 0.010681, 0.156011, 1.232386, -0.039588, 0.150715, 1.456774, 0.110231,
-        0.134071, 1.232386, 0.113104, 0.134071, 1.232386, 0.113104, -2e-6, 1.455858, 0.15026,
-        -2e-6, 1.228824, 0.147699, 0.16699, 1.504943, -0.104881, -2e-6, 1.228824, -0.08242, -2e-6,
-        1.520128, -0.142679, -2e-6, 1.667634, 0.090502, 0.060363, 1.695242, 0.117183, -2e-6,
-        1.685263, 0.169948, 0.060363, 1.695242, 0.117183, -2e-6, 1.781039, 0.186901, -2e-6,
-        1.685263, 0.169948, 0.069369, 1.712631, 0.010681, 0.087972, 1.800795, 0.119867, 0.060363,
-        1.695242, 0.117183, -2e-6, 1.694542, -0.06919, 0.087972, 1.795755, -0.055406, 0.069369,
-        1.712631, 0.010681, 0.087972, 1.795755, -0.055406, 0.079111, 1.917483, 0.114259, 0.087972,
-        1.800795, 0.119867, -2e-6, 1.898342, 0.172222, 0.079111, 1.917483, 0.114259, -2e-6,
-        1.955331, 0.103664, -2e-6, 1.921414, -0.098006, -2e-6, 1.965068, -0.015152, 0.079111,
-        1.921374, -0.056769, 0.079111, 1.917483, 0.114259, -2e-6, 1.965068, -0.015152, -2e-6,
-        1.955331, 0.103664, -2e-6, 1.921414, -0.098006, 0.087972, 1.795755, -0.055406, -2e-6,
-        1.771472, -0.116269, 0.087972, 1.800795, 0.119867, -2e-6, 1.898342, 0.172222, -2e-6,
-        1.781039, 0.186901, -2e-6, 1.898342, 0.172222, -0.087972, 1.800795, 0.119867, -2e-6,
-        1.781039, 0.186901, -0.087972, 1.795755, -0.055406, -2e-6, 1.921414, -0.098006, -2e-6,
-        1.771472, -0.116269, -0.079111, 1.917483, 0.114259, -2e-6, 1.965068, -0.015152, -0.079111,
-        1.921374, -0.056769, -2e-6, 1.921414, -0.098006, -0.079111, 1.921374, -0.056769, -2e-6,
-        1.965068, -0.015152, -2e-6, 1.898342, 0.172222, -2e-6, 1.955331, 0.103664, -0.079111,
-        1.917483, 0.114259, -0.079111, 1.917483, 0.114259, -0.087972, 1.795755, -0.055406,
-        -0.087972, 1.800795, 0.119867, -0.087972, 1.795755, -0.055406, -2e-6, 1.694542, -0.06919,
-        -0.069369, 1.712631, 0.010681, -0.069369, 1.712631, 0.010681, -0.087972, 1.800795,
-        0.119867, -0.087972, 1.795755, -0.055406, -0.060363, 1.695242, 0.117183, -2e-6, 1.781039,
-        0.186901, -0.087972, 1.800795, 0.119867, -2e-6, 1.667634, 0.090502, -0.060363, 1.695242,
-        0.117183, -0.069369, 1.712631, 0.010681, -0.16699, 1.504943, -0.104881, -2e-6, 1.228824,
-        -0.08242, -0.156011, 1.232386, -0.039588, -0.134071, 1.232386, 0.113104, -2e-6, 1.455858,
-        0.15026, -0.150715, 1.456774, 0.110231, -0.150715, 1.456774, 0.110231, -0.156011, 1.232386,
-        -0.039588, -0.134071, 1.232386, 0.113104, -0.063202, 1.664156, 0.010681, -2e-6, 1.694542,
-        -0.06919, -2e-6, 1.621071, -0.090806, -2e-6, 1.667634, 0.090502, -0.063202, 1.664156,
-        0.010681, -2e-6, 1.617999, 0.095863, -0.073951, 0.58598, -0.012714, -2e-6, 0.933496,
-        0.136309, -0.095652, 0.601477, 0.102363, -0.182317, 0.601477, 0.102363, -2e-6, 0.933496,
-        0.136309, -0.163228, 1.074129, 0.13785, -0.192826, 0.939277, -0.069293, -0.073951, 0.58598,
-        -0.012714, -0.204017, 0.58598, -0.012714, -0.182317, 0.601477, 0.102363, -0.192826,
-        0.939277, -0.069293, -0.204017, 0.58598, -0.012714, -0.309371, 0.943399, -0.026862,
-        -0.38593, 0.862996, -0.041366, -0.351491, 1.004101, -0.013116, -0.287162, 0.94535,
-        0.087145, -0.320725, 0.856594, -0.039569, -0.309371, 0.943399, -0.026862, -0.403225,
-        0.89242, 0.118543, -0.323792, 0.830571, 0.118543, -0.287162, 0.94535, 0.087145, -0.38593,
-        0.862996, -0.041366, -0.323792, 0.830571, 0.118543, -0.403225, 0.89242, 0.118543,
-        -0.312077, 0.975234, 0.056556, -0.287162, 0.94535, 0.087145, -0.309371, 0.943399,
-        -0.026862, -0.38593, 0.862996, -0.041366, -0.348743, 1.005957, 0.056556, -0.351491,
-        1.004101, -0.013116, -0.403225, 0.89242, 0.118543, -0.312077, 0.975234, 0.056556,
-        -0.348743, 1.005957, 0.056556, -0.16699, 1.504943, -0.104881, -0.29292, 1.288112,
-        -0.068648, -0.256865, 1.547362, -0.064066, -0.150715, 1.456774, 0.110231, -0.294348,
-        1.26189, 0.025875, -0.216796, 1.213177, 0.033581, -0.172347, 1.627023, -0.07553, -0.16699,
-        1.504943, -0.104881, -0.256865, 1.547362, -0.064066, -0.237228, 1.542835, 0.067978,
-        -0.29292, 1.288112, -0.068648, -0.294348, 1.26189, 0.025875, -0.237228, 1.542835, 0.067978,
-        -0.150715, 1.456774, 0.110231, -0.129298, 1.621634, 0.090801, -2e-6, 1.455858, 0.15026,
-        -0.129298, 1.621634, 0.090801, -0.150715, 1.456774, 0.110231, -0.16699, 1.504943,
-        -0.104881, -2e-6, 1.621071, -0.090806, -2e-6, 1.520128, -0.142679, -0.172347, 1.627023,
-        -0.07553, -0.063202, 1.664156, 0.010681, -2e-6, 1.621071, -0.090806, -0.129298, 1.621634,
-        0.090801, -2e-6, 1.617999, 0.095863, -0.063202, 1.664156, 0.010681, -0.063202, 1.664156,
-        0.010681, -0.172347, 1.627023, -0.07553, -0.129298, 1.621634, 0.090801, -0.243687,
-        1.181918, -0.078929, -0.312077, 0.975234, 0.056556, -0.309371, 0.943399, -0.026862,
-        -0.294348, 1.26189, 0.025875, -0.351491, 1.004101, -0.013116, -0.348743, 1.005957,
-        0.056556, -0.243687, 1.181918, -0.078929, -0.351491, 1.004101, -0.013116, -0.31255,
-        1.261717, -0.078929, -0.216796, 1.213177, 0.033581, -0.348743, 1.005957, 0.056556,
-        -0.312077, 0.975234, 0.056556, -0.224522, 1.238198, -0.064175, -0.216796, 1.213177,
-        0.033581, -0.243687, 1.181918, -0.078929, -0.29292, 1.288112, -0.068648, -0.31255,
-        1.261717, -0.078929, -0.294348, 1.26189, 0.025875, -0.224522, 1.238198, -0.064175,
-        -0.31255, 1.261717, -0.078929, -0.29292, 1.288112, -0.068648, -0.150715, 1.456774,
-        0.110231, -0.224522, 1.238198, -0.064175, -0.16699, 1.504943, -0.104881, -2e-6, 1.228824,
-        0.147699, -0.163228, 1.074129, 0.13785, -2e-6, 1.042395, 0.146343, -0.163061, 1.061983,
-        -0.071234, -2e-6, 1.228824, -0.08242, -2e-6, 1.035529, -0.129773, -0.163228, 1.074129,
-        0.13785, -0.156011, 1.232386, -0.039588, -0.163061, 1.061983, -0.071234, -2e-6, 0.931916,
-        -0.092766, -0.163061, 1.061983, -0.071234, -2e-6, 1.035529, -0.129773, -0.192826, 0.939277,
-        -0.069293, -0.163228, 1.074129, 0.13785, -0.163061, 1.061983, -0.071234, -2e-6, 0.933496,
-        0.136309, -2e-6, 1.042395, 0.146343, -0.163228, 1.074129, 0.13785, -0.073951, 0.58598,
-        -0.012714, -0.086958, 0.549551, 0.115117, -0.075994, 0.446094, -0.058787, -0.095652,
-        0.601477, 0.102363, -0.086958, 0.549551, 0.115117, -0.073951, 0.58598, -0.012714,
-        -0.204017, 0.58598, -0.012714, -0.217823, 0.446094, -0.058787, -0.191011, 0.549551,
-        0.115117, -0.204017, 0.58598, -0.012714, -0.191011, 0.549551, 0.115117, -0.182317,
-        0.601477, 0.102363, -0.075994, 0.446094, -0.058787, -0.130214, 0.101878, 0.060545,
-        -0.145872, 0.101878, -0.041803, -0.216925, 0.101878, 0.060545, -0.217823, 0.446094,
-        -0.058787, -0.201267, 0.101878, -0.041803, -0.204017, 0.58598, -0.012714, -0.075994,
-        0.446094, -0.058787, -0.217823, 0.446094, -0.058787, -0.217823, 0.446094, -0.058787,
-        -0.145872, 0.101878, -0.041803, -0.201267, 0.101878, -0.041803, -0.191011, 0.549551,
-        0.115117, -0.130214, 0.101878, 0.060545, -0.086958, 0.549551, 0.115117, -0.182317,
-        0.601477, 0.102363, -0.086958, 0.549551, 0.115117, -0.095652, 0.601477, 0.102363,
-        -0.128584, 0.001012, -0.038395, -0.230771, 0.001012, 0.08831, -0.218556, 0.001012,
-        -0.056775, -0.132578, 0.001012, 0.114505, -0.231377, -0.001025, 0.224849, -0.230771,
-        0.001012, 0.08831, -0.216925, 0.101878, 0.060545, -0.218556, 0.001012, -0.056775,
-        -0.230771, 0.001012, 0.08831, -0.130214, 0.101878, 0.060545, -0.128584, 0.001012,
-        -0.038395, -0.145872, 0.101878, -0.041803, -0.137877, 0.042153, 0.249172, -0.132578,
-        0.001012, 0.114505, -0.130214, 0.101878, 0.060545, -0.230771, 0.001012, 0.08831, -0.21673,
-        0.03405, 0.235835, -0.216925, 0.101878, 0.060545, -0.21673, 0.03405, 0.235835, -0.115763,
-        -0.001025, 0.242068, -0.137877, 0.042153, 0.249172, -0.130214, 0.101878, 0.060545,
-        -0.21673, 0.03405, 0.235835, -0.137877, 0.042153, 0.249172, -0.128584, 0.001012, -0.038395,
-        -0.201267, 0.101878, -0.041803, -0.145872, 0.101878, -0.041803, 0.129298, 1.621634,
-        0.090801, 0.237228, 1.542835, 0.067978, 0.172348, 1.627023, -0.07553, 0.172348, 1.627023,
-        -0.07553, 0.237228, 1.542835, 0.067978, 0.256866, 1.547362, -0.064066, -0.256865, 1.547362,
-        -0.064066, -0.237228, 1.542835, 0.067978, -0.172347, 1.627023, -0.07553, -0.237228,
-        1.542835, 0.067978, -0.129298, 1.621634, 0.090801, -0.172347, 1.627023, -0.07553, 0.201267,
-        0.101878, -0.041803, 0.218556, 0.001012, -0.056775, 0.128584, 0.001012, -0.038395,
-        0.130214, 0.101878, 0.060545, 0.137877, 0.042153, 0.249172, 0.21673, 0.03405, 0.235835,
-        0.115763, -0.001025, 0.242068, 0.231377, -0.001025, 0.224849, 0.21673, 0.03405, 0.235835,
-        0.21673, 0.03405, 0.235835, 0.231377, -0.001025, 0.224849, 0.230771, 0.001012, 0.08831,
-        0.132578, 0.001012, 0.114505, 0.115763, -0.001025, 0.242068, 0.137877, 0.042153, 0.249172,
-        0.128584, 0.001012, -0.038395, 0.132578, 0.001012, 0.114505, 0.130214, 0.101878, 0.060545,
-        0.216925, 0.101878, 0.060545, 0.230771, 0.001012, 0.08831, 0.218556, 0.001012, -0.056775,
-        0.132578, 0.001012, 0.114505, 0.230771, 0.001012, 0.08831, 0.231377, -0.001025, 0.224849,
-        0.230771, 0.001012, 0.08831, 0.132578, 0.001012, 0.114505, 0.128584, 0.001012, -0.038395,
-        0.086958, 0.549551, 0.115117, 0.191011, 0.549551, 0.115117, 0.182317, 0.601477, 0.102363,
-        0.130214, 0.101878, 0.060545, 0.216925, 0.101878, 0.060545, 0.191011, 0.549551, 0.115117,
-        0.145873, 0.101878, -0.041803, 0.075994, 0.446094, -0.058787, 0.217823, 0.446094,
-        -0.058787, 0.204018, 0.58598, -0.012714, 0.217823, 0.446094, -0.058787, 0.075994, 0.446094,
-        -0.058787, 0.216925, 0.101878, 0.060545, 0.201267, 0.101878, -0.041803, 0.217823, 0.446094,
-        -0.058787, 0.130214, 0.101878, 0.060545, 0.086958, 0.549551, 0.115117, 0.075994, 0.446094,
-        -0.058787, 0.163061, 1.061983, -0.071234, 0.192827, 0.939277, -0.069293, -2e-6, 0.931916,
-        -0.092766, 0.163228, 1.074129, 0.13785, 0.163061, 1.061983, -0.071234, 0.156011, 1.232386,
-        -0.039588, 0.163061, 1.061983, -0.071234, -2e-6, 1.035529, -0.129773, -2e-6, 1.228824,
-        -0.08242, 0.163228, 1.074129, 0.13785, 0.134071, 1.232386, 0.113104, -2e-6, 1.228824,
-        0.147699, 0.150715, 1.456774, 0.110231, 0.16699, 1.504943, -0.104881, 0.224522, 1.238198,
-        -0.064175, 0.312551, 1.261717, -0.078929, 0.243687, 1.181918, -0.078929, 0.224522,
-        1.238198, -0.064175, 0.216796, 1.213177, 0.033581, 0.312077, 0.975234, 0.056556, 0.348743,
-        1.005957, 0.056556, 0.351491, 1.004101, -0.013116, 0.309371, 0.943399, -0.026862, 0.243687,
-        1.181918, -0.078929, 0.294348, 1.26189, 0.025875, 0.348743, 1.005957, 0.056556, 0.351491,
-        1.004101, -0.013116, 0.243687, 1.181918, -0.078929, 0.309371, 0.943399, -0.026862,
-        0.312077, 0.975234, 0.056556, 0.16699, 1.504943, -0.104881, -2e-6, 1.520128, -0.142679,
-        -2e-6, 1.621071, -0.090806, 0.129298, 1.621634, 0.090801, -2e-6, 1.617999, 0.095863, -2e-6,
-        1.455858, 0.15026, 0.237228, 1.542835, 0.067978, 0.294348, 1.26189, 0.025875, 0.29292,
-        1.288112, -0.068648, 0.150715, 1.456774, 0.110231, 0.216796, 1.213177, 0.033581, 0.294348,
-        1.26189, 0.025875, 0.16699, 1.504943, -0.104881, 0.256866, 1.547362, -0.064066, 0.29292,
-        1.288112, -0.068648, 0.312077, 0.975234, 0.056556, 0.287163, 0.94535, 0.087145, 0.403226,
-        0.89242, 0.118543, 0.348743, 1.005957, 0.056556, 0.403226, 0.89242, 0.118543, 0.385931,
-        0.862996, -0.041366, 0.385931, 0.862996, -0.041366, 0.403226, 0.89242, 0.118543, 0.323792,
-        0.830571, 0.118543, 0.287163, 0.94535, 0.087145, 0.309371, 0.943399, -0.026862, 0.320725,
-        0.856594, -0.039569, 0.385931, 0.862996, -0.041366, 0.320725, 0.856594, -0.039569,
-        0.309371, 0.943399, -0.026862, 0.192827, 0.939277, -0.069293, 0.163228, 1.074129, 0.13785,
-        0.182317, 0.601477, 0.102363, 0.073952, 0.58598, -0.012714, -2e-6, 0.931916, -0.092766,
-        0.192827, 0.939277, -0.069293, 0.182317, 0.601477, 0.102363, 0.163228, 1.074129, 0.13785,
-        -2e-6, 0.933496, 0.136309, -2e-6, 0.933496, 0.136309, -2e-6, 0.931916, -0.092766, 0.073952,
-        0.58598, -0.012714, 0.063203, 1.664156, 0.010681, 0.069369, 1.712631, 0.010681, -2e-6,
-        1.667634, 0.090502, 0.063203, 1.664156, 0.010681, -2e-6, 1.621071, -0.090806, -2e-6,
-        1.694542, -0.06919, 0.156011, 1.232386, -0.039588, 0.16699, 1.504943, -0.104881, 0.150715,
-        1.456774, 0.110231, 0.134071, 1.232386, 0.113104, 0.150715, 1.456774, 0.110231, -2e-6,
-        1.455858, 0.15026, 0.16699, 1.504943, -0.104881, 0.156011, 1.232386, -0.039588, -2e-6,
-        1.228824, -0.08242, -2e-6, 1.667634, 0.090502, 0.069369, 1.712631, 0.010681, 0.060363,
-        1.695242, 0.117183, 0.060363, 1.695242, 0.117183, 0.087972, 1.800795, 0.119867, -2e-6,
-        1.781039, 0.186901, 0.069369, 1.712631, 0.010681, 0.087972, 1.795755, -0.055406, 0.087972,
-        1.800795, 0.119867, -2e-6, 1.694542, -0.06919, -2e-6, 1.771472, -0.116269, 0.087972,
-        1.795755, -0.055406, 0.087972, 1.795755, -0.055406, 0.079111, 1.921374, -0.056769,
-        0.079111, 1.917483, 0.114259, 0.079111, 1.917483, 0.114259, 0.079111, 1.921374, -0.056769,
-        -2e-6, 1.965068, -0.015152, -2e-6, 1.921414, -0.098006, 0.079111, 1.921374, -0.056769,
-        0.087972, 1.795755, -0.055406, 0.087972, 1.800795, 0.119867, 0.079111, 1.917483, 0.114259,
-        -2e-6, 1.898342, 0.172222, -2e-6, 1.898342, 0.172222, -0.079111, 1.917483, 0.114259,
-        -0.087972, 1.800795, 0.119867, -0.087972, 1.795755, -0.055406, -0.079111, 1.921374,
-        -0.056769, -2e-6, 1.921414, -0.098006, -0.079111, 1.917483, 0.114259, -2e-6, 1.955331,
-        0.103664, -2e-6, 1.965068, -0.015152, -0.079111, 1.917483, 0.114259, -0.079111, 1.921374,
-        -0.056769, -0.087972, 1.795755, -0.055406, -0.087972, 1.795755, -0.055406, -2e-6, 1.771472,
-        -0.116269, -2e-6, 1.694542, -0.06919, -0.069369, 1.712631, 0.010681, -0.060363, 1.695242,
-        0.117183, -0.087972, 1.800795, 0.119867, -0.060363, 1.695242, 0.117183, -2e-6, 1.685263,
-        0.169948, -2e-6, 1.781039, 0.186901, -2e-6, 1.667634, 0.090502, -2e-6, 1.685263, 0.169948,
-        -0.060363, 1.695242, 0.117183, -0.16699, 1.504943, -0.104881, -2e-6, 1.520128, -0.142679,
-        -2e-6, 1.228824, -0.08242, -0.134071, 1.232386, 0.113104, -2e-6, 1.228824, 0.147699, -2e-6,
-        1.455858, 0.15026, -0.150715, 1.456774, 0.110231, -0.16699, 1.504943, -0.104881, -0.156011,
-        1.232386, -0.039588, -0.063202, 1.664156, 0.010681, -0.069369, 1.712631, 0.010681, -2e-6,
-        1.694542, -0.06919, -2e-6, 1.667634, 0.090502, -0.069369, 1.712631, 0.010681, -0.063202,
-        1.664156, 0.010681, -0.073951, 0.58598, -0.012714, -2e-6, 0.931916, -0.092766, -2e-6,
-        0.933496, 0.136309, -0.182317, 0.601477, 0.102363, -0.095652, 0.601477, 0.102363, -2e-6,
-        0.933496, 0.136309, -0.192826, 0.939277, -0.069293, -2e-6, 0.931916, -0.092766, -0.073951,
-        0.58598, -0.012714, -0.182317, 0.601477, 0.102363, -0.163228, 1.074129, 0.13785, -0.192826,
-        0.939277, -0.069293, -0.309371, 0.943399, -0.026862, -0.320725, 0.856594, -0.039569,
-        -0.38593, 0.862996, -0.041366, -0.287162, 0.94535, 0.087145, -0.323792, 0.830571, 0.118543,
-        -0.320725, 0.856594, -0.039569, -0.38593, 0.862996, -0.041366, -0.320725, 0.856594,
-        -0.039569, -0.323792, 0.830571, 0.118543, -0.38593, 0.862996, -0.041366, -0.403225,
-        0.89242, 0.118543, -0.348743, 1.005957, 0.056556, -0.403225, 0.89242, 0.118543, -0.287162,
-        0.94535, 0.087145, -0.312077, 0.975234, 0.056556, -0.16699, 1.504943, -0.104881, -0.224522,
-        1.238198, -0.064175, -0.29292, 1.288112, -0.068648, -0.150715, 1.456774, 0.110231,
-        -0.237228, 1.542835, 0.067978, -0.294348, 1.26189, 0.025875, -0.237228, 1.542835, 0.067978,
-        -0.256865, 1.547362, -0.064066, -0.29292, 1.288112, -0.068648, -2e-6, 1.455858, 0.15026,
-        -2e-6, 1.617999, 0.095863, -0.129298, 1.621634, 0.090801, -0.16699, 1.504943, -0.104881,
-        -0.172347, 1.627023, -0.07553, -2e-6, 1.621071, -0.090806, -0.243687, 1.181918, -0.078929,
-        -0.216796, 1.213177, 0.033581, -0.312077, 0.975234, 0.056556, -0.294348, 1.26189, 0.025875,
-        -0.31255, 1.261717, -0.078929, -0.351491, 1.004101, -0.013116, -0.243687, 1.181918,
-        -0.078929, -0.309371, 0.943399, -0.026862, -0.351491, 1.004101, -0.013116, -0.216796,
-        1.213177, 0.033581, -0.294348, 1.26189, 0.025875, -0.348743, 1.005957, 0.056556, -0.224522,
-        1.238198, -0.064175, -0.243687, 1.181918, -0.078929, -0.31255, 1.261717, -0.078929,
-        -0.150715, 1.456774, 0.110231, -0.216796, 1.213177, 0.033581, -0.224522, 1.238198,
-        -0.064175, -2e-6, 1.228824, 0.147699, -0.134071, 1.232386, 0.113104, -0.163228, 1.074129,
-        0.13785, -0.163061, 1.061983, -0.071234, -0.156011, 1.232386, -0.039588, -2e-6, 1.228824,
-        -0.08242, -0.163228, 1.074129, 0.13785, -0.134071, 1.232386, 0.113104, -0.156011, 1.232386,
-        -0.039588, -2e-6, 0.931916, -0.092766, -0.192826, 0.939277, -0.069293, -0.163061, 1.061983,
-        -0.071234, -0.075994, 0.446094, -0.058787, -0.086958, 0.549551, 0.115117, -0.130214,
-        0.101878, 0.060545, -0.216925, 0.101878, 0.060545, -0.191011, 0.549551, 0.115117,
-        -0.217823, 0.446094, -0.058787, -0.204017, 0.58598, -0.012714, -0.073951, 0.58598,
-        -0.012714, -0.075994, 0.446094, -0.058787, -0.217823, 0.446094, -0.058787, -0.075994,
-        0.446094, -0.058787, -0.145872, 0.101878, -0.041803, -0.191011, 0.549551, 0.115117,
-        -0.216925, 0.101878, 0.060545, -0.130214, 0.101878, 0.060545, -0.182317, 0.601477,
-        0.102363, -0.191011, 0.549551, 0.115117, -0.086958, 0.549551, 0.115117, -0.128584,
-        0.001012, -0.038395, -0.132578, 0.001012, 0.114505, -0.230771, 0.001012, 0.08831,
-        -0.132578, 0.001012, 0.114505, -0.115763, -0.001025, 0.242068, -0.231377, -0.001025,
-        0.224849, -0.216925, 0.101878, 0.060545, -0.201267, 0.101878, -0.041803, -0.218556,
-        0.001012, -0.056775, -0.130214, 0.101878, 0.060545, -0.132578, 0.001012, 0.114505,
-        -0.128584, 0.001012, -0.038395, -0.137877, 0.042153, 0.249172, -0.115763, -0.001025,
-        0.242068, -0.132578, 0.001012, 0.114505, -0.230771, 0.001012, 0.08831, -0.231377,
-        -0.001025, 0.224849, -0.21673, 0.03405, 0.235835, -0.21673, 0.03405, 0.235835, -0.231377,
-        -0.001025, 0.224849, -0.115763, -0.001025, 0.242068, -0.130214, 0.101878, 0.060545,
-        -0.216925, 0.101878, 0.060545, -0.21673, 0.03405, 0.235835, -0.128584, 0.001012, -0.038395,
-        -0.218556, 0.001012, -0.056775, -0.201267, 0.101878, -0.041803,
-    ]
+//  Every three indices into `vertices` make up a triangle.
+pub struct Mesh<'a> {
+    pub vertices: &'a [Vec3<Real>],
+    pub indices: &'a [u16],
+    //  Unit normal of each triangle in model space, so lighting and back faces need no
+    //  cross products or square roots per frame.
+    pub face_normals: &'a [Vec3<Real>],
+    //  One color per triangle, replacing the color of the render pass.
+    pub face_colors: Option<&'a [Color]>,
+    //  Model space bounds, computed when the mesh is generated.
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    pub sphere_center: Vec3,
+    pub sphere_radius: f32,
 }
+
+include!(concat!(env!("OUT_DIR"), "/models.rs"));
@@ -0,0 +1,609 @@
+//  Wavefront .obj parsing shared by `build.rs` and `load_obj`.
+//  Not part of the game itself, so this is free to use std.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[path = "palette.rs"]
+mod palette;
+
+pub use palette::PALETTE;
+
+pub struct Obj {
+    pub vertices: Vec<[f32; 3]>,
+    //  Zero based indices into `vertices`.
+    pub faces: Vec<[usize; 3]>,
+    //  Indices into `PALETTE`, one per face, if the file uses materials.
+    pub face_colors: Option<Vec<usize>>,
+    //  The line each face came from, for warnings.
+    pub face_lines: Vec<usize>,
+}
+
+pub struct Metadata {
+    pub bounds_min: [f32; 3],
+    pub bounds_max: [f32; 3],
+    pub sphere_center: [f32; 3],
+    pub sphere_radius: f32,
+    //  Line numbers paired with what looks off there.
+    pub warnings: Vec<(usize, String)>,
+}
+
+#[derive(Debug)]
+pub struct ObjError {
+    pub path: PathBuf,
+    //  Zero when the problem isn't on any one line.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.path.display(), self.message)
+        } else {
+            write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, ObjError> {
+    fs::read_to_string(path).map_err(|e| ObjError {
+        path: path.to_owned(),
+        line: 0,
+        message: e.to_string(),
+    })
+}
+
+//  Loads the .obj at `path` along with any .mtl files it references next to it.
+pub fn load(path: &Path) -> Result<Obj, ObjError> {
+    let source = read(path)?;
+
+    let mut obj = Obj {
+        vertices: vec![],
+        faces: vec![],
+        face_colors: None,
+        face_lines: vec![],
+    };
+    let mut materials = HashMap::new();
+    let mut current_color = None;
+    let mut face_colors = vec![];
+
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index + 1;
+        let error = |message: String| ObjError {
+            path: path.to_owned(),
+            line: line_number,
+            message,
+        };
+
+        //  Anything after a `#` is a comment.
+        let line = line.split('#').next().unwrap();
+        let mut split = line.split_whitespace();
+        match split.next() {
+            //  Extra components such as `w` or vertex colors are ignored.
+            Some("v") => {
+                let mut vertex = [0.0; 3];
+                for component in vertex.iter_mut() {
+                    let value = split
+                        .next()
+                        .ok_or_else(|| error("vertex needs three components".into()))?;
+                    *component = value
+                        .parse::<f32>()
+                        .map_err(|_| error(format!("invalid vertex component `{}`", value)))?;
+                }
+                obj.vertices.push(vertex);
+            }
+            //  Polygons are fanned out from their first vertex, so they should be convex.
+            Some("f") => {
+                let mut polygon = vec![];
+                for value in split {
+                    //  Only the position of `v`, `v/vt`, `v//vn` and `v/vt/vn` matters.
+                    let position = value.split('/').next().unwrap();
+                    let parsed = position
+                        .parse::<isize>()
+                        .map_err(|_| error(format!("invalid face index `{}`", value)))?;
+                    let vertex_count = obj.vertices.len() as isize;
+                    //  Negative indices count backwards from the latest vertex.
+                    let index = if parsed < 0 {
+                        vertex_count + parsed
+                    } else {
+                        parsed - 1
+                    };
+                    if parsed == 0 || !(0..vertex_count).contains(&index) {
+                        return Err(error(format!("face index {} is out of range", parsed)));
+                    }
+                    polygon.push(index as usize);
+                }
+                if polygon.len() < 3 {
+                    return Err(error("face needs at least three vertices".into()));
+                }
+                for i in 1..polygon.len() - 1 {
+                    obj.faces.push([polygon[0], polygon[i], polygon[i + 1]]);
+                    face_colors.push(current_color);
+                    obj.face_lines.push(line_number);
+                }
+            }
+            Some("mtllib") => {
+                for name in split {
+                    let mtl_path = path.parent().unwrap_or(Path::new("")).join(name);
+                    load_mtl(&mtl_path, &mut materials)?;
+                }
+            }
+            Some("usemtl") => {
+                let name = split
+                    .next()
+                    .ok_or_else(|| error("usemtl needs a material name".into()))?;
+                let color = materials
+                    .get(name)
+                    .ok_or_else(|| error(format!("unknown material `{}`", name)))?;
+                current_color = Some(*color);
+            }
+            _ => {}
+        }
+    }
+
+    //  Faces before the first `usemtl` get the same color as a material without a `Kd`.
+    if face_colors.iter().any(Option::is_some) {
+        let colors = face_colors
+            .into_iter()
+            .map(|color| color.unwrap_or_else(default_color));
+        obj.face_colors = Some(colors.collect());
+    }
+
+    Ok(obj)
+}
+
+//  Only the diffuse color of each material is kept, snapped to the palette.
+fn load_mtl(path: &Path, materials: &mut HashMap<String, usize>) -> Result<(), ObjError> {
+    let source = read(path)?;
+
+    let mut current_name: Option<String> = None;
+    for (line_index, line) in source.lines().enumerate() {
+        let error = |message: String| ObjError {
+            path: path.to_owned(),
+            line: line_index + 1,
+            message,
+        };
+
+        let line = line.split('#').next().unwrap();
+        let mut split = line.split_whitespace();
+        match split.next() {
+            Some("newmtl") => {
+                let name = split
+                    .next()
+                    .ok_or_else(|| error("newmtl needs a material name".into()))?;
+                materials.insert(name.to_owned(), default_color());
+                current_name = Some(name.to_owned());
+            }
+            Some("Kd") => {
+                let name = current_name
+                    .as_ref()
+                    .ok_or_else(|| error("Kd before any newmtl".into()))?;
+                let mut diffuse = [0.0; 3];
+                for component in diffuse.iter_mut() {
+                    let value = split
+                        .next()
+                        .ok_or_else(|| error("Kd needs three components".into()))?;
+                    *component = value
+                        .parse::<f32>()
+                        .map_err(|_| error(format!("invalid Kd component `{}`", value)))?;
+                }
+                materials.insert(name.clone(), nearest_color(diffuse));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+//  Without a `Kd` of its own, a material is the usual light gray.
+fn default_color() -> usize {
+    nearest_color([0.8, 0.8, 0.8])
+}
+
+//  Picks the closest palette color using the "redmean" weighted distance,
+//  which is a cheap but decent stand-in for how different colors look.
+pub fn nearest_color(diffuse: [f32; 3]) -> usize {
+    let [r, g, b] = diffuse.map(|c| c.clamp(0.0, 1.0) * 255.0);
+    let distance = |[pr, pg, pb]: [u8; 3]| {
+        let mean_r = (r + pr as f32) / 2.0;
+        let dr = r - pr as f32;
+        let dg = g - pg as f32;
+        let db = b - pb as f32;
+        (2.0 + mean_r / 256.0) * dr * dr
+            + 4.0 * dg * dg
+            + (2.0 + (255.0 - mean_r) / 256.0) * db * db
+    };
+    (0..PALETTE.len())
+        .min_by(|&a, &b| distance(PALETTE[a].1).total_cmp(&distance(PALETTE[b].1)))
+        .unwrap()
+}
+
+impl Obj {
+    pub fn metadata(&self) -> Metadata {
+        let mut bounds_min = [0.0; 3];
+        let mut bounds_max = [0.0; 3];
+        if let Some(first) = self.vertices.first() {
+            bounds_min = *first;
+            bounds_max = *first;
+        }
+        for vertex in &self.vertices {
+            for i in 0..3 {
+                bounds_min[i] = bounds_min[i].min(vertex[i]);
+                bounds_max[i] = bounds_max[i].max(vertex[i]);
+            }
+        }
+
+        //  Not the tightest sphere, but centered on the bounds it is close enough for culling.
+        let sphere_center = [0, 1, 2].map(|i| (bounds_min[i] + bounds_max[i]) / 2.0);
+        let sphere_radius = self
+            .vertices
+            .iter()
+            .map(|vertex| length(sub(*vertex, sphere_center)))
+            .fold(0.0, f32::max);
+
+        let mut warnings = vec![];
+        let mut edges = HashMap::new();
+        for (face, &line) in self.faces.iter().zip(&self.face_lines) {
+            let [a, b, c] = face.map(|index| self.vertices[index]);
+            let normal = cross(sub(b, a), sub(c, a));
+            if face[0] == face[1]
+                || face[1] == face[2]
+                || face[0] == face[2]
+                || length(normal) < 1e-10
+            {
+                warnings.push((line, "degenerate face with no area".into()));
+                continue;
+            }
+
+            //  Neighbours that agree on winding walk their shared edge in opposite directions.
+            for edge in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                if let Some(other_line) = edges.insert(edge, line) {
+                    warnings.push((
+                        line,
+                        format!(
+                            "face is wound inconsistently with the face on line {}",
+                            other_line
+                        ),
+                    ));
+                    break;
+                }
+            }
+        }
+
+        Metadata {
+            bounds_min,
+            bounds_max,
+            sphere_center,
+            sphere_radius,
+            warnings,
+        }
+    }
+
+    //  Writes a `name()` function returning the mesh, laid out the way rustfmt would.
+    pub fn to_rust(&self, name: &str, metadata: &Metadata) -> Result<String, String> {
+        if name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(format!("`{}` is not a snake_case identifier", name));
+        }
+        if self.vertices.len() > u16::MAX as usize + 1 {
+            return Err("too many vertices for u16 indices".into());
+        }
+
+        let mut out = String::new();
+        writeln!(out, "//  {} triangles.", self.faces.len()).unwrap();
+        writeln!(out, "pub fn {}() -> &'static Mesh<'static> {{", name).unwrap();
+        writeln!(out, "    const MESH: Mesh = Mesh {{").unwrap();
+        let vertices = self.vertices.iter().map(|&vertex| real_vec3_source(vertex));
+        write_array(&mut out, "vertices: &", vertices.collect(), ",");
+        let indices = self.faces.iter().flatten().map(|index| index.to_string());
+        write_array(&mut out, "indices: &", indices.collect(), ",");
+        //  Degenerate faces get a zero normal, they have no area to draw anyway.
+        let face_normals = self.faces.iter().map(|face| {
+            let [a, b, c] = face.map(|index| self.vertices[index]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let length = length(normal);
+            if length < 1e-10 {
+                real_vec3_source([0.0; 3])
+            } else {
+                real_vec3_source(normal.map(|v| v / length))
+            }
+        });
+        write_array(&mut out, "face_normals: &", face_normals.collect(), ",");
+        if let Some(face_colors) = &self.face_colors {
+            let face_colors = face_colors
+                .iter()
+                .map(|&color| format!("Color::{}", PALETTE[color].0));
+            write_array(&mut out, "face_colors: Some(&", face_colors.collect(), "),");
+        } else {
+            writeln!(out, "        face_colors: None,").unwrap();
+        }
+        writeln!(
+            out,
+            "        bounds_min: {},",
+            vec3_source(metadata.bounds_min)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        bounds_max: {},",
+            vec3_source(metadata.bounds_max)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        sphere_center: {},",
+            vec3_source(metadata.sphere_center)
+        )
+        .unwrap();
+        writeln!(out, "        sphere_radius: {:?},", metadata.sphere_radius).unwrap();
+        writeln!(out, "    }};").unwrap();
+        writeln!(out, "    &MESH").unwrap();
+        writeln!(out, "}}").unwrap();
+        Ok(out)
+    }
+}
+
+fn vec3_source(v: [f32; 3]) -> String {
+    format!("Vec3::new({:?}, {:?}, {:?})", v[0], v[1], v[2])
+}
+
+fn real_vec3_source(v: [f32; 3]) -> String {
+    format!(
+        "Vec3::new(real({:?}), real({:?}), real({:?}))",
+        v[0], v[1], v[2]
+    )
+}
+
+//  rustfmt keeps a narrow array on one line, otherwise it packs short items into rows
+//  and gives longer ones a line each. Rows leave a column spare.
+fn write_array(out: &mut String, prefix: &str, items: Vec<String>, suffix: &str) {
+    const INDENT: &str = "        ";
+    const MAX_WIDTH: usize = 99;
+    const ARRAY_WIDTH: usize = 60;
+    const SHORT_ITEM_WIDTH: usize = 10;
+
+    let joined = items.join(", ");
+    if joined.len() <= ARRAY_WIDTH {
+        writeln!(out, "{}{}[{}]{}", INDENT, prefix, joined, suffix).unwrap();
+        return;
+    }
+
+    writeln!(out, "{}{}[", INDENT, prefix).unwrap();
+    if items.iter().all(|item| item.len() <= SHORT_ITEM_WIDTH) {
+        let mut line = String::new();
+        for item in items {
+            if !line.is_empty() && INDENT.len() + 4 + line.len() + 1 + item.len() + 1 > MAX_WIDTH {
+                writeln!(out, "{}    {}", INDENT, line).unwrap();
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&item);
+            line.push(',');
+        }
+        writeln!(out, "{}    {}", INDENT, line).unwrap();
+    } else {
+        for item in items {
+            writeln!(out, "{}    {},", INDENT, item).unwrap();
+        }
+    }
+    writeln!(out, "{}]{}", INDENT, suffix).unwrap();
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(v: [f32; 3]) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //  Writes `files` into a directory of the test's own and returns the path of the first.
+    fn write(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("room-obj-{}-{}", std::process::id(), test));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+        dir.join(files[0].0)
+    }
+
+    fn load_source(test: &str, source: &str) -> Result<Obj, ObjError> {
+        load(&write(test, &[("mesh.obj", source)]))
+    }
+
+    const SQUARE: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv -1 0.5 0\n";
+
+    #[test]
+    fn polygons_are_fanned_from_their_first_vertex() {
+        let obj = load_source("fan", &format!("{}f 1 2 3 4\nf 1 2 3 4 5\n", SQUARE)).unwrap();
+        assert_eq!(
+            obj.faces,
+            [[0, 1, 2], [0, 2, 3], [0, 1, 2], [0, 2, 3], [0, 3, 4]]
+        );
+        assert_eq!(obj.face_lines, [6, 6, 7, 7, 7]);
+        assert!(obj.face_colors.is_none());
+    }
+
+    #[test]
+    fn only_positions_are_taken_from_faces() {
+        let source = format!(
+            "{}f 1/1/1 2/2/1 3/3/1\nf 1//1 3//1 4//1\nf 1/1 2/2 3/3\nf -5 -4 -3\n",
+            SQUARE
+        );
+        let obj = load_source("forms", &source).unwrap();
+        assert_eq!(obj.faces, [[0, 1, 2], [0, 2, 3], [0, 1, 2], [0, 1, 2]]);
+    }
+
+    #[test]
+    fn comments_and_tabs_are_skipped() {
+        let source = "# a triangle\n\
+                      v\t0 0 0 # the origin\n\
+                      v 1\t0\t0\n\
+                      \tv 0 1 0\n\
+                      \n\
+                      f 1 2 3# no space before the comment\n";
+        let obj = load_source("comments", source).unwrap();
+        assert_eq!(
+            obj.vertices,
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+        );
+        assert_eq!(obj.faces, [[0, 1, 2]]);
+        assert_eq!(obj.face_lines, [6]);
+    }
+
+    #[test]
+    fn bad_faces_report_their_line() {
+        for (test, face, message) in [
+            ("past-the-end", "f 1 2 6", "face index 6 is out of range"),
+            ("zero", "f 0 1 2", "face index 0 is out of range"),
+            (
+                "before-the-start",
+                "f -6 1 2",
+                "face index -6 is out of range",
+            ),
+            ("not-a-number", "f 1 x/1 2", "invalid face index `x/1`"),
+            ("too-short", "f 1 2", "face needs at least three vertices"),
+        ] {
+            let source = format!("{}\nf 1 2 3\n{}\n", SQUARE, face);
+            let error = load_source(test, &source).err().unwrap();
+            assert_eq!(error.line, 8, "{}", error);
+            assert_eq!(error.message, message);
+            assert!(error.to_string().ends_with(&format!(":8: {}", message)));
+        }
+    }
+
+    fn palette_index(name: &str) -> usize {
+        PALETTE
+            .iter()
+            .position(|&(entry, _)| entry == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn palette_colors_map_to_themselves() {
+        for (i, (_, rgb)) in PALETTE.iter().enumerate() {
+            assert_eq!(nearest_color(rgb.map(|c| c as f32 / 255.0)), i);
+        }
+    }
+
+    #[test]
+    fn near_colors_pick_the_closest_entry() {
+        for (diffuse, name) in [
+            ([0.9, 0.15, 0.25], "Red3"),
+            ([0.1, 0.1, 0.85], "Blue5"),
+            ([0.2, 0.7, 0.95], "Blue7"),
+            ([1.0, 0.95, 0.0], "Yellow6"),
+            ([0.3, 0.3, 0.35], "GrayL"),
+            ([0.8, 0.8, 0.8], "Gray2"),
+            //  Out of range components are clamped first.
+            ([-1.0, -1.0, -1.0], "Gray0"),
+            ([2.0, 2.0, 2.0], "Gray2"),
+        ] {
+            assert_eq!(nearest_color(diffuse), palette_index(name), "{:?}", diffuse);
+        }
+    }
+
+    #[test]
+    fn missing_usemtl_or_kd_falls_back_to_the_default() {
+        let mtl = "newmtl red\nKd 0.9 0.15 0.25\nnewmtl plain\n";
+        let triangle = "v 0 0 0\nv 1 0 0\nv 0 1 0\nmtllib mesh.mtl\n";
+        let source = format!(
+            "{}f 1 2 3\nusemtl red\nf 1 2 3\nusemtl plain\nf 1 2 3\n",
+            triangle
+        );
+        let path = write("materials", &[("mesh.obj", &source), ("mesh.mtl", mtl)]);
+        let obj = load(&path).unwrap();
+        let (red, plain) = (palette_index("Red3"), default_color());
+        assert_eq!(obj.face_colors, Some(vec![plain, red, plain]));
+
+        //  With no `usemtl` at all, the mesh is left to the color it is drawn with.
+        let source = format!("{}f 1 2 3\n", triangle);
+        let path = write("no-usemtl", &[("mesh.obj", &source), ("mesh.mtl", mtl)]);
+        assert_eq!(load(&path).unwrap().face_colors, None);
+    }
+
+    //  Corners of the box from (-1, -2, -3) to (3, 2, 1) and its center, faces from line 10 on.
+    fn corners(faces: &[[usize; 3]]) -> Obj {
+        Obj {
+            vertices: vec![
+                [-1.0, -2.0, -3.0],
+                [3.0, 2.0, 1.0],
+                [3.0, -2.0, -3.0],
+                [-1.0, 2.0, -3.0],
+                [1.0, 0.0, -1.0],
+            ],
+            faces: faces.to_vec(),
+            face_colors: None,
+            face_lines: (10..10 + faces.len()).collect(),
+        }
+    }
+
+    #[test]
+    fn metadata_bounds_the_mesh() {
+        let obj = corners(&[[0, 2, 1], [2, 0, 3]]);
+        let metadata = obj.metadata();
+        assert_eq!(metadata.bounds_min, [-1.0, -2.0, -3.0]);
+        assert_eq!(metadata.bounds_max, [3.0, 2.0, 1.0]);
+        assert_eq!(metadata.sphere_center, [1.0, 0.0, -1.0]);
+        assert_eq!(metadata.sphere_radius, 12.0f32.sqrt());
+        assert!(metadata.warnings.is_empty(), "{:?}", metadata.warnings);
+
+        let source = obj.to_rust("corners", &metadata).unwrap();
+        assert!(
+            source.starts_with("//  2 triangles.\npub fn corners() -> &'static Mesh<'static> {")
+        );
+        assert!(source.contains("        bounds_min: Vec3::new(-1.0, -2.0, -3.0),\n"));
+        assert!(source.contains("        sphere_radius: 3.4641016,\n"));
+        assert!(obj.to_rust("Corners", &metadata).is_err());
+    }
+
+    #[test]
+    fn degenerate_faces_are_warned_about() {
+        //  A repeated vertex, and a vertex halfway between the other two.
+        let metadata = corners(&[[0, 2, 1], [0, 0, 1], [0, 4, 1]]).metadata();
+        assert_eq!(
+            metadata.warnings,
+            [
+                (11, "degenerate face with no area".to_string()),
+                (12, "degenerate face with no area".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn flipped_faces_are_warned_about() {
+        //  Both walk the edge from 0 to 2 the same way, so one of them faces the wrong way.
+        let metadata = corners(&[[0, 2, 1], [0, 2, 3]]).metadata();
+        assert_eq!(
+            metadata.warnings,
+            [(
+                11,
+                "face is wound inconsistently with the face on line 10".to_string()
+            )]
+        );
+    }
+}
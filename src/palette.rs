@@ -0,0 +1,19 @@
+//  The sprig palette, in the same order as `Color`.
+//  Kept free of dependencies so that `build.rs` and `load_obj` can share it.
+pub const PALETTE: [(&str, [u8; 3]); 15] = [
+    ("Gray0", [0, 0, 0]),
+    ("GrayL", [73, 80, 87]),
+    ("Gray1", [145, 151, 156]),
+    ("Gray2", [248, 249, 250]),
+    ("Red3", [235, 44, 71]),
+    ("RedC", [139, 65, 46]),
+    ("Blue7", [25, 177, 248]),
+    ("Blue5", [19, 21, 224]),
+    ("Yellow6", [254, 230, 16]),
+    ("YellowF", [149, 140, 50]),
+    ("Green4", [45, 225, 62]),
+    ("GreenD", [29, 148, 16]),
+    ("Pink8", [245, 109, 187]),
+    ("PurpleH", [170, 58, 197]),
+    ("Orange9", [245, 113, 23]),
+];
@@ -0,0 +1,335 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+//  What `math` is written against, so the same functions run on `f32` or on `Fixed`.
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    //  Nudge used to keep divisions away from zero.
+    const EPSILON: Self;
+
+    fn from_f32(v: f32) -> Self;
+    fn to_f32(self) -> f32;
+    //  Q16.16 bits, what the fixed point rasterizer walks edges in.
+    fn to_q16_16(self) -> i32;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn acos(self) -> Self;
+}
+
+//  The scalar the per vertex work in `graphics` runs on.
+//  The RP2040 has no FPU, `fixed-math` trades some precision for integer only transforms.
+#[cfg(not(feature = "fixed-math"))]
+pub type Real = f32;
+#[cfg(feature = "fixed-math")]
+pub type Real = Fixed;
+
+//  `Real::from_f32` for constants, so generated meshes are stored ready to transform.
+#[cfg(not(feature = "fixed-math"))]
+pub const fn real(v: f32) -> Real {
+    v
+}
+#[cfg(feature = "fixed-math")]
+pub const fn real(v: f32) -> Real {
+    Fixed((v * (1 << Fixed::FRACTION_BITS) as f32) as i32)
+}
+
+//  Kept out of the parent module so `Scalar` isn't in scope and `sqrt` and friends resolve to
+//  std on the host and `micromath` on the Sprig, like everywhere else.
+mod float {
+    #[allow(unused_imports)]
+    use micromath::F32Ext;
+
+    impl super::Scalar for f32 {
+        const ZERO: Self = 0.0;
+        const ONE: Self = 1.0;
+        const EPSILON: Self = 0.00001;
+
+        #[inline(always)]
+        fn from_f32(v: f32) -> Self {
+            v
+        }
+
+        #[inline(always)]
+        fn to_f32(self) -> f32 {
+            self
+        }
+
+        #[inline(always)]
+        fn to_q16_16(self) -> i32 {
+            (self * 65536.0) as i32
+        }
+
+        #[inline(always)]
+        fn sqrt(self) -> Self {
+            self.sqrt()
+        }
+
+        #[inline(always)]
+        fn sin(self) -> Self {
+            self.sin()
+        }
+
+        #[inline(always)]
+        fn cos(self) -> Self {
+            self.cos()
+        }
+
+        #[inline(always)]
+        fn tan(self) -> Self {
+            self.tan()
+        }
+
+        #[inline(always)]
+        fn acos(self) -> Self {
+            self.acos()
+        }
+    }
+}
+
+//  Q16.16, good for +-32768 with a resolution of 1/65536.
+//  Products and quotients go through `i64`. Anything past the range saturates at its ends, like
+//  `f32` going to infinity, rather than wrapping around to the other sign.
+#[cfg_attr(not(feature = "fixed-math"), allow(dead_code))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(i32);
+
+#[cfg_attr(not(feature = "fixed-math"), allow(dead_code))]
+impl Fixed {
+    const FRACTION_BITS: u32 = 16;
+}
+
+impl Scalar for Fixed {
+    const ZERO: Self = Fixed(0);
+    const ONE: Self = Fixed(1 << Self::FRACTION_BITS);
+    const EPSILON: Self = Fixed(1);
+
+    #[inline(always)]
+    fn from_f32(v: f32) -> Self {
+        Fixed((v * (1 << Self::FRACTION_BITS) as f32) as i32)
+    }
+
+    #[inline(always)]
+    fn to_f32(self) -> f32 {
+        self.0 as f32 / (1 << Self::FRACTION_BITS) as f32
+    }
+
+    #[inline(always)]
+    fn to_q16_16(self) -> i32 {
+        self.0
+    }
+
+    //  Bit by bit integer square root of the value widened to Q32.32, negatives give zero.
+    fn sqrt(self) -> Self {
+        let mut value = (self.0.max(0) as u64) << Self::FRACTION_BITS;
+        let mut result = 0u64;
+        let mut bit = 1u64 << 62;
+        while bit > value {
+            bit >>= 2;
+        }
+        while bit != 0 {
+            if value >= result + bit {
+                value -= result + bit;
+                result = (result >> 1) + bit;
+            } else {
+                result >>= 1;
+            }
+            bit >>= 2;
+        }
+        Fixed(result as i32)
+    }
+
+    //  Only used while building matrices and orientations, a few times per frame, so these go
+    //  through `f32`.
+    #[inline(always)]
+    fn sin(self) -> Self {
+        Self::from_f32(self.to_f32().sin())
+    }
+
+    #[inline(always)]
+    fn cos(self) -> Self {
+        Self::from_f32(self.to_f32().cos())
+    }
+
+    #[inline(always)]
+    fn tan(self) -> Self {
+        Self::from_f32(self.to_f32().tan())
+    }
+
+    #[inline(always)]
+    fn acos(self) -> Self {
+        Self::from_f32(self.to_f32().acos())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Fixed(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Fixed(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let product = (self.0 as i64 * rhs.0 as i64) >> Self::FRACTION_BITS;
+        Fixed(product.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    //  Dividing by zero saturates instead of panicking, matching `f32` going to infinity.
+    #[inline(always)]
+    fn div(self, rhs: Self) -> Self {
+        if rhs.0 == 0 {
+            return Fixed(if self.0 < 0 { i32::MIN } else { i32::MAX });
+        }
+        let quotient = ((self.0 as i64) << Self::FRACTION_BITS) / rhs.0 as i64;
+        Fixed(quotient.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Fixed {
+    #[inline(always)]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nanorand::{Rng, WyRand};
+
+    const CASES: usize = 10000;
+
+    //  Uniform in `-range..range`.
+    fn random(rng: &mut WyRand, range: f32) -> Fixed {
+        Fixed::from_f32((rng.generate::<f32>() * 2.0 - 1.0) * range)
+    }
+
+    //  Within a step of `Fixed`, or of `f32` once the values get big enough for that to be
+    //  coarser.
+    fn assert_agrees(fixed: Fixed, float: f32, what: &str) {
+        let tolerance = 1.0 / 65536.0 + float.abs() * f32::EPSILON * 2.0;
+        assert!(
+            (fixed.to_f32() - float).abs() <= tolerance,
+            "{}: {} against {}",
+            what,
+            fixed.to_f32(),
+            float
+        );
+    }
+
+    #[test]
+    fn add_and_sub_agree_with_f32() {
+        let mut rng = WyRand::new_seed(1);
+        for _ in 0..CASES {
+            let (a, b) = (random(&mut rng, 16000.0), random(&mut rng, 16000.0));
+            assert_agrees(a + b, a.to_f32() + b.to_f32(), "add");
+            assert_agrees(a - b, a.to_f32() - b.to_f32(), "sub");
+        }
+    }
+
+    #[test]
+    fn mul_agrees_with_f32() {
+        let mut rng = WyRand::new_seed(2);
+        for _ in 0..CASES {
+            let (a, b) = (random(&mut rng, 180.0), random(&mut rng, 180.0));
+            assert_agrees(a * b, a.to_f32() * b.to_f32(), "mul");
+        }
+    }
+
+    #[test]
+    fn div_agrees_with_f32() {
+        let mut rng = WyRand::new_seed(3);
+        for _ in 0..CASES {
+            let a = random(&mut rng, 1000.0);
+            let b = random(&mut rng, 100.0);
+            if b.to_f32().abs() < 0.05 {
+                continue;
+            }
+            assert_agrees(a / b, a.to_f32() / b.to_f32(), "div");
+        }
+    }
+
+    #[test]
+    fn sqrt_agrees_with_f32() {
+        let mut rng = WyRand::new_seed(4);
+        for _ in 0..CASES {
+            let a = Fixed::from_f32(rng.generate::<f32>() * 32000.0);
+            assert_agrees(a.sqrt(), a.to_f32().sqrt(), "sqrt");
+        }
+        assert_eq!(Fixed::from_f32(-4.0).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn overflow_saturates() {
+        let big = Fixed::from_f32(30000.0);
+        assert_eq!(big + big, Fixed(i32::MAX));
+        assert_eq!(-big - big, Fixed(i32::MIN));
+        assert_eq!(big * big, Fixed(i32::MAX));
+        assert_eq!(big * -big, Fixed(i32::MIN));
+        assert_eq!(big / Fixed::from_f32(0.01), Fixed(i32::MAX));
+        assert_eq!(big / Fixed::ZERO, Fixed(i32::MAX));
+        assert_eq!(-Fixed(i32::MIN), Fixed(i32::MAX));
+    }
+}